@@ -0,0 +1,84 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use aoc_lib::grid::{bits::BitGrid, Direction, Grid, Pos};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const SIZE: usize = 256;
+
+/// A fixed pseudo-random pattern with roughly a third of the cells on.
+fn start() -> Grid<bool> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut grid = Grid::new(SIZE, SIZE, false);
+    for row in 0..SIZE {
+        for col in 0..SIZE {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            grid[Pos::new(row, col)] = state % 3 == 1;
+        }
+    }
+    grid
+}
+
+fn life_step(grid: &Grid<bool>) -> Grid<bool> {
+    let mut next = Grid::new(grid.rows(), grid.cols(), false);
+    for row in 0..grid.rows() {
+        for col in 0..grid.cols() {
+            let pos = Pos::new(row, col);
+            let alive = grid.neighbours8(pos).filter(|p| *p.value).count();
+            next[pos] = alive == 3 || (alive == 2 && grid[pos]);
+        }
+    }
+    next
+}
+
+fn life_step_bits(grid: &BitGrid) -> BitGrid {
+    let three = grid.neighbours_in(&Direction::ALL, &[3]);
+    let two = grid.neighbours_in(&Direction::ALL, &[2]);
+    &three | &(&two & grid)
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn bench_life(c: &mut Criterion) {
+    let grid = start();
+    let bits = BitGrid::from_grid(&grid);
+    assert_eq!(life_step(&grid), life_step_bits(&bits).to_grid());
+
+    let mut group = c.benchmark_group("life_step");
+    group.bench_function("Grid<bool>", |b| b.iter(|| life_step(black_box(&grid))));
+    group.bench_function("BitGrid", |b| b.iter(|| life_step_bits(black_box(&bits))));
+    group.finish();
+}
+
+fn bench_count(c: &mut Criterion) {
+    let grid = start();
+    let bits = BitGrid::from_grid(&grid);
+
+    let mut group = c.benchmark_group("count_ones");
+    group.bench_function("Grid<bool>", |b| {
+        b.iter(|| black_box(&grid).iter().filter(|p| p.value).count())
+    });
+    group.bench_function("BitGrid", |b| b.iter(|| black_box(&bits).count_ones()));
+    group.finish();
+}
+
+fn bench_hash(c: &mut Criterion) {
+    let grid = start();
+    let bits = BitGrid::from_grid(&grid);
+
+    let mut group = c.benchmark_group("hash");
+    group.bench_function("Grid<bool>", |b| b.iter(|| hash(black_box(&grid))));
+    group.bench_function("BitGrid", |b| b.iter(|| hash(black_box(&bits))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_life, bench_count, bench_hash);
+criterion_main!(benches);
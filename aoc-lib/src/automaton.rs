@@ -0,0 +1,472 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    ops::Range,
+};
+
+use itertools::Either;
+
+use crate::{
+    grid::{bits::BitGrid, sparse::SignedPos, sparse::SparseGrid, Direction, Grid, Pos},
+    hex::{Hex, HexMap},
+    point::{PointN, PointSet},
+};
+
+/// Which cells count as neighbours of a cell.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Neighbourhood {
+    /// Cells that differ by one step along a single axis.
+    VonNeumann,
+    /// Cells that differ by at most one step along every axis, diagonals included.
+    Moore,
+}
+
+/// The space the cells of an [`Automaton`] live in.
+pub trait CellSpace {
+    type Pos: Copy + Eq + Hash;
+    type Cell: Clone + PartialEq;
+
+    /// `true` if the space is unbounded and only stores the cells that differ from the
+    /// background. The neighbours of stored cells are then updated as well, since they are the
+    /// only other cells that can change.
+    const SPARSE: bool;
+
+    /// Every stored cell. For a dense space that is every cell.
+    fn positions(&self) -> Vec<Self::Pos>;
+
+    fn neighbours(
+        &self,
+        pos: Self::Pos,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = Self::Pos>;
+
+    fn cell(&self, pos: Self::Pos) -> Self::Cell;
+
+    fn set_cell(&mut self, pos: Self::Pos, cell: Self::Cell);
+}
+
+impl<T> CellSpace for Grid<T>
+where
+    T: Clone + PartialEq,
+{
+    type Pos = Pos;
+    type Cell = T;
+    const SPARSE: bool = false;
+
+    fn positions(&self) -> Vec<Pos> {
+        self.iter().map(|p| p.pos).collect()
+    }
+
+    fn neighbours(&self, pos: Pos, neighbourhood: Neighbourhood) -> impl Iterator<Item = Pos> {
+        match neighbourhood {
+            Neighbourhood::VonNeumann => Either::Left(self.neighbours4(pos).map(|n| n.pos)),
+            Neighbourhood::Moore => Either::Right(self.neighbours8(pos).map(|n| n.pos)),
+        }
+    }
+
+    fn cell(&self, pos: Pos) -> T {
+        self[pos].clone()
+    }
+
+    fn set_cell(&mut self, pos: Pos, cell: T) {
+        self[pos] = cell;
+    }
+}
+
+impl CellSpace for BitGrid {
+    type Pos = Pos;
+    type Cell = bool;
+    const SPARSE: bool = false;
+
+    fn positions(&self) -> Vec<Pos> {
+        (0..self.rows())
+            .flat_map(|row| (0..self.cols()).map(move |col| Pos::new(row, col)))
+            .collect()
+    }
+
+    fn neighbours(&self, pos: Pos, neighbourhood: Neighbourhood) -> impl Iterator<Item = Pos> {
+        let directions: &[Direction] = match neighbourhood {
+            Neighbourhood::VonNeumann => &Direction::ORTHOGONAL,
+            Neighbourhood::Moore => &Direction::ALL,
+        };
+        directions
+            .iter()
+            .filter_map(move |&dir| pos.step(dir))
+            .filter(|&p| self.contains(p))
+    }
+
+    fn cell(&self, pos: Pos) -> bool {
+        self.get(pos)
+    }
+
+    fn set_cell(&mut self, pos: Pos, cell: bool) {
+        self.set(pos, cell);
+    }
+}
+
+impl<T> CellSpace for SparseGrid<T>
+where
+    T: Clone + PartialEq,
+{
+    type Pos = SignedPos;
+    type Cell = T;
+    const SPARSE: bool = true;
+
+    fn positions(&self) -> Vec<SignedPos> {
+        self.iter().map(|p| p.pos).collect()
+    }
+
+    fn neighbours(
+        &self,
+        pos: SignedPos,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = SignedPos> {
+        match neighbourhood {
+            Neighbourhood::VonNeumann => Either::Left(self.neighbours4(pos).map(|n| n.pos)),
+            Neighbourhood::Moore => Either::Right(self.neighbours8(pos).map(|n| n.pos)),
+        }
+    }
+
+    fn cell(&self, pos: SignedPos) -> T {
+        self.get(pos).clone()
+    }
+
+    fn set_cell(&mut self, pos: SignedPos, cell: T) {
+        if cell == *self.default_value() {
+            self.remove(pos);
+        } else {
+            self.insert(pos, cell);
+        }
+    }
+}
+
+/// Every hexagon has the same six neighbours, so the neighbourhood is ignored.
+impl<T> CellSpace for HexMap<T>
+where
+    T: Clone + PartialEq,
+{
+    type Pos = Hex;
+    type Cell = T;
+    const SPARSE: bool = true;
+
+    fn positions(&self) -> Vec<Hex> {
+        self.iter().map(|(hex, _)| hex).collect()
+    }
+
+    fn neighbours(&self, hex: Hex, _: Neighbourhood) -> impl Iterator<Item = Hex> {
+        hex.neighbours()
+    }
+
+    fn cell(&self, hex: Hex) -> T {
+        self.get(hex).clone()
+    }
+
+    fn set_cell(&mut self, hex: Hex, cell: T) {
+        if cell == *self.default_value() {
+            self.remove(hex);
+        } else {
+            self.insert(hex, cell);
+        }
+    }
+}
+
+/// A cell is alive if it is in the set.
+impl<const D: usize> CellSpace for PointSet<D> {
+    type Pos = PointN<D>;
+    type Cell = bool;
+    const SPARSE: bool = true;
+
+    fn positions(&self) -> Vec<PointN<D>> {
+        self.iter().collect()
+    }
+
+    fn neighbours(
+        &self,
+        point: PointN<D>,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = PointN<D>> {
+        match neighbourhood {
+            Neighbourhood::VonNeumann => Either::Left(point.von_neumann_neighbours()),
+            Neighbourhood::Moore => Either::Right(point.moore_neighbours()),
+        }
+    }
+
+    fn cell(&self, point: PointN<D>) -> bool {
+        self.contains(point)
+    }
+
+    fn set_cell(&mut self, point: PointN<D>, alive: bool) {
+        if alive {
+            self.insert(point);
+        } else {
+            self.remove(point);
+        }
+    }
+}
+
+/// How [`Automaton::run_until_repeat`] ended up in a state it had already seen.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Repeat {
+    /// The generation where the repeated state first appeared.
+    pub first: usize,
+    /// The generation where it appeared again, which is the current generation.
+    pub second: usize,
+}
+
+impl Repeat {
+    pub fn cycle_length(&self) -> usize {
+        self.second - self.first
+    }
+}
+
+/// Runs a cellular automaton. Every generation, `rule` gets the position and value of a cell and
+/// the values of its neighbours, and returns the value of the cell in the next generation. All
+/// cells are updated at once.
+pub struct Automaton<S, F>
+where
+    S: CellSpace,
+{
+    space: S,
+    neighbourhood: Neighbourhood,
+    rule: F,
+    generation: usize,
+    changes: Vec<(S::Pos, S::Cell)>,
+    /// The neighbours of every cell that is updated in a step, one cell after the other, and
+    /// where in that list the neighbours of each cell are. Kept between steps to reuse the memory.
+    neighbours: Vec<S::Pos>,
+    cells: Vec<(S::Pos, Range<usize>)>,
+}
+
+impl<S, F> Automaton<S, F>
+where
+    S: CellSpace,
+    F: FnMut(S::Pos, &S::Cell, &[S::Cell]) -> S::Cell,
+{
+    pub fn new(space: S, neighbourhood: Neighbourhood, rule: F) -> Self {
+        Self {
+            space,
+            neighbourhood,
+            rule,
+            generation: 0,
+            changes: Vec::new(),
+            neighbours: Vec::new(),
+            cells: Vec::new(),
+        }
+    }
+
+    pub fn space(&self) -> &S {
+        &self.space
+    }
+
+    pub fn into_space(self) -> S {
+        self.space
+    }
+
+    /// Number of steps taken so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Number of stored cells for which `alive` holds. In a sparse space the cells that aren't
+    /// stored are never counted.
+    pub fn population<P>(&self, mut alive: P) -> usize
+    where
+        P: FnMut(&S::Cell) -> bool,
+    {
+        self.space
+            .positions()
+            .into_iter()
+            .filter(|&pos| alive(&self.space.cell(pos)))
+            .count()
+    }
+
+    /// Computes the next generation. Returns `false` if no cell changed, i.e. the automaton has
+    /// reached a fixed point.
+    pub fn step(&mut self) -> bool {
+        self.neighbours.clear();
+        self.cells.clear();
+        let positions = self.space.positions();
+        for &pos in &positions {
+            self.add_cell(pos);
+        }
+        if S::SPARSE {
+            let stored = positions.into_iter().collect::<HashSet<_>>();
+            let unstored = self
+                .neighbours
+                .iter()
+                .copied()
+                .filter(|n| !stored.contains(n))
+                .collect::<HashSet<_>>();
+            for pos in unstored {
+                self.add_cell(pos);
+            }
+        }
+
+        let mut values = Vec::new();
+        for (pos, range) in &self.cells {
+            values.clear();
+            values.extend(
+                self.neighbours[range.clone()]
+                    .iter()
+                    .map(|&n| self.space.cell(n)),
+            );
+            let current = self.space.cell(*pos);
+            let next = (self.rule)(*pos, &current, &values);
+            if next != current {
+                self.changes.push((*pos, next));
+            }
+        }
+
+        self.generation += 1;
+        let changed = !self.changes.is_empty();
+        for (pos, cell) in self.changes.drain(..) {
+            self.space.set_cell(pos, cell);
+        }
+        changed
+    }
+
+    /// Adds `pos` to the cells that are updated in this step.
+    fn add_cell(&mut self, pos: S::Pos) {
+        let start = self.neighbours.len();
+        self.neighbours
+            .extend(self.space.neighbours(pos, self.neighbourhood));
+        self.cells.push((pos, start..self.neighbours.len()));
+    }
+
+    /// Takes `generations` steps, stopping early at a fixed point.
+    pub fn run(&mut self, generations: usize) {
+        let target = self.generation + generations;
+        while self.generation < target {
+            if !self.step() {
+                self.generation = target;
+            }
+        }
+    }
+
+    /// Steps until no cell changes any more and returns the generation of the fixed point.
+    /// Never returns if the automaton doesn't settle.
+    pub fn run_until_stable(&mut self) -> usize {
+        while self.step() {}
+        self.generation - 1
+    }
+
+    /// Steps until the automaton is in a state it has been in before. A fixed point counts as a
+    /// cycle of length 1.
+    pub fn run_until_repeat(&mut self) -> Repeat
+    where
+        S: Clone + Eq + Hash,
+    {
+        let mut seen = HashMap::from([(self.space.clone(), self.generation)]);
+        loop {
+            self.step();
+            if let Some(&first) = seen.get(&self.space) {
+                return Repeat {
+                    first,
+                    second: self.generation,
+                };
+            }
+            seen.insert(self.space.clone(), self.generation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        grid::{Grid, Pos, Topology},
+        hex::{Hex, HexDirection, HexMap},
+        point::{Point3, PointSet},
+    };
+
+    use super::{Automaton, Neighbourhood};
+
+    fn life(_: Pos, &alive: &bool, neighbours: &[bool]) -> bool {
+        let count = neighbours.iter().filter(|&&n| n).count();
+        count == 3 || (alive && count == 2)
+    }
+
+    fn parse(s: &str) -> Grid<bool> {
+        s.parse::<Grid<char>>().unwrap().map(|&c| c == '#')
+    }
+
+    #[test]
+    fn blinker() {
+        let grid = parse(".....\n..#..\n..#..\n..#..\n.....");
+        let mut automaton = Automaton::new(grid.clone(), Neighbourhood::Moore, life);
+        assert!(automaton.step());
+        assert_eq!(
+            *automaton.space(),
+            parse(".....\n.....\n.###.\n.....\n.....")
+        );
+        assert_eq!(automaton.population(|&alive| alive), 3);
+
+        let repeat = automaton.run_until_repeat();
+        assert_eq!(repeat.first, 1);
+        assert_eq!(repeat.second, 3);
+        assert_eq!(repeat.cycle_length(), 2);
+        automaton.step();
+        assert!(*automaton.space() == grid);
+    }
+
+    #[test]
+    fn fixed_point() {
+        let grid = parse("....\n.##.\n.#..\n....");
+        let mut automaton = Automaton::new(grid, Neighbourhood::Moore, life);
+        assert_eq!(automaton.run_until_stable(), 1);
+        assert_eq!(automaton.population(|&alive| alive), 4);
+        assert!(!automaton.step());
+    }
+
+    #[test]
+    fn wrapping_glider() {
+        let grid = parse(".#....\n..#...\n###...\n......\n......\n......")
+            .with_topology(Topology::Wrapping);
+        let mut automaton = Automaton::new(grid, Neighbourhood::Moore, life);
+        // A glider moves one cell diagonally every 4 generations, so it's back after 24.
+        assert_eq!(automaton.run_until_repeat().cycle_length(), 24);
+    }
+
+    #[test]
+    fn rule_sees_position() {
+        let grid = Grid::new(3, 3, 0);
+        let mut automaton = Automaton::new(
+            grid,
+            Neighbourhood::VonNeumann,
+            |pos: Pos, _: &u32, n: &[u32]| (pos.row() + pos.col()) as u32 + n.len() as u32,
+        );
+        automaton.step();
+        assert_eq!(automaton.space()[Pos::new(0, 0)], 2);
+        assert_eq!(automaton.space()[Pos::new(1, 1)], 6);
+        assert!(!automaton.step());
+    }
+
+    #[test]
+    fn conway_cubes() {
+        let space = [[1, 0, 0], [2, 1, 0], [0, 2, 0], [1, 2, 0], [2, 2, 0]]
+            .into_iter()
+            .map(Point3::new)
+            .collect::<PointSet<3>>();
+        let mut automaton = Automaton::new(space, Neighbourhood::Moore, |_, &alive, n: &[bool]| {
+            let count = n.iter().filter(|&&n| n).count();
+            count == 3 || (alive && count == 2)
+        });
+        automaton.run(6);
+        assert_eq!(automaton.generation(), 6);
+        assert_eq!(automaton.space().len(), 112);
+    }
+
+    #[test]
+    fn hex_life() {
+        let mut map = HexMap::new(false);
+        map.insert(Hex::ORIGIN, true);
+        map.insert(Hex::ORIGIN.step(HexDirection::East), true);
+        let mut automaton = Automaton::new(map, Neighbourhood::Moore, |_, &black, n: &[bool]| {
+            let count = n.iter().filter(|&&n| n).count();
+            count == 2 || (black && count == 1)
+        });
+        automaton.step();
+        // Both tiles stay black, and so do the two tiles touching both of them.
+        assert_eq!(automaton.population(|&black| black), 4);
+        assert_eq!(automaton.space().len(), 4);
+    }
+}
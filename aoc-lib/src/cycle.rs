@@ -0,0 +1,209 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// The states of a sequence repeat from step `start` on, every `length` steps.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step that has the same state as step `n`. Always less than
+    /// `start + length`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// The value at step `n` of a metric that grows by the same amount every time around the
+    /// cycle, like the height of a tower or a score. `history` has the value for every step up to
+    /// and including `start + length`.
+    pub fn extrapolate(&self, history: &[i64], n: usize) -> i64 {
+        if n < history.len() {
+            return history[n];
+        }
+        let end = self.start + self.length;
+        let laps = ((n - self.start) / self.length) as i64;
+        history[self.reduce(n)] + laps * (history[end] - history[self.start])
+    }
+}
+
+/// Brent's algorithm, which only keeps a couple of states around and doesn't need them to be
+/// hashable. `step` is called a few times more than there are states before the cycle comes
+/// around, so it should be cheap.
+pub fn brent<S, F>(initial: &S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // Find the length by letting the hare run ahead of a tortoise that teleports to it
+    // whenever the distance between them doubles.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // With the hare `length` steps ahead, they meet at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Steps `state` in place until `key` returns something it has returned before, and leaves it
+/// at step `start + length`. `key` can be the whole state, or whatever part of it decides
+/// what happens next, e.g. the top rows of a tower and the position in the input.
+pub fn find_cycle_by_key<S, K, F, G>(state: &mut S, mut step: F, mut key: G) -> Cycle
+where
+    K: Eq + Hash,
+    F: FnMut(&mut S),
+    G: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut n = 0;
+    loop {
+        if let Some(start) = seen.insert(key(state), n) {
+            return Cycle {
+                start,
+                length: n - start,
+            };
+        }
+        step(state);
+        n += 1;
+    }
+}
+
+/// Steps `state` from step 0 to the same state as step `n`, skipping whole laps of the cycle
+/// once one has been found.
+pub fn advance<S, K, F, G>(state: &mut S, mut step: F, mut key: G, n: usize)
+where
+    K: Eq + Hash,
+    F: FnMut(&mut S),
+    G: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut current = 0;
+    while current < n {
+        if let Some(start) = seen.insert(key(state), current) {
+            let remaining = (n - current) % (current - start);
+            for _ in 0..remaining {
+                step(state);
+            }
+            return;
+        }
+        step(state);
+        current += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Cycle;
+
+    /// 0, 1, 2, 3, 4 and then 5, 6, 7, 8, 9, 10, 11 over and over.
+    fn next(&x: &u64) -> u64 {
+        if x == 11 {
+            5
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn brent() {
+        assert_eq!(
+            super::brent(&0, next),
+            Cycle {
+                start: 5,
+                length: 7
+            }
+        );
+        assert_eq!(
+            super::brent(&3, |_| 3),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn by_key() {
+        let mut state = (0, 0);
+        let cycle = super::find_cycle_by_key(
+            &mut state,
+            |(x, steps)| {
+                *x = next(x);
+                *steps += 1;
+            },
+            |&(x, _)| x,
+        );
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 5,
+                length: 7
+            }
+        );
+        assert_eq!(state, (5, 12));
+    }
+
+    #[test]
+    fn reduce() {
+        let cycle = super::brent(&0, next);
+        for n in [0, 4, 5, 11, 12, 1000] {
+            let state = (0..n).fold(0, |x, _| next(&x));
+            assert_eq!((0..cycle.reduce(n)).fold(0, |x, _| next(&x)), state);
+        }
+    }
+
+    #[test]
+    fn advance() {
+        let mut state = 0;
+        super::advance(&mut state, |x| *x = next(x), |&x| x, 1_000_000_000);
+        assert_eq!(state, 5 + (1_000_000_000 - 5) % 7);
+
+        let mut state = 0;
+        super::advance(&mut state, |x| *x = next(x), |&x| x, 3);
+        assert_eq!(state, 3);
+    }
+
+    #[test]
+    fn extrapolate() {
+        // The sum of all states so far.
+        let mut history = vec![0];
+        let mut state = 0;
+        let cycle = super::find_cycle_by_key(
+            &mut state,
+            |x| {
+                *x = next(x);
+                history.push(history.last().unwrap() + *x as i64);
+            },
+            |&x| x,
+        );
+        let n = 100;
+        let expected = (0..n).fold((0, 0), |(x, sum), _| (next(&x), sum + next(&x) as i64));
+        assert_eq!(cycle.extrapolate(&history, n), expected.1);
+        assert_eq!(cycle.extrapolate(&history, 3), 6);
+    }
+}
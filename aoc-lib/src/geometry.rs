@@ -0,0 +1,221 @@
+use crate::{
+    math::gcd,
+    point::{BoundingBox, Point2},
+};
+
+/// Where a point is relative to a polygon.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Twice the area of the polygon, from the shoelace formula. In this module a polygon is given by
+/// its corners in order, and the last corner connects back to the first. The result is positive
+/// if the corners go counter-clockwise when the y axis points up.
+pub fn double_signed_area(corners: &[Point2]) -> i64 {
+    edges(corners).map(|(a, b)| a[0] * b[1] - b[0] * a[1]).sum()
+}
+
+/// The area of the polygon. Polygons with only horizontal and vertical edges always have a
+/// whole area, for others it's rounded down.
+pub fn area(corners: &[Point2]) -> i64 {
+    double_signed_area(corners).abs() / 2
+}
+
+/// Number of integer points on the edges of the polygon, corners included.
+pub fn boundary_points(corners: &[Point2]) -> i64 {
+    edges(corners)
+        .map(|(a, b)| gcd(a[0].abs_diff(b[0]), a[1].abs_diff(b[1])) as i64)
+        .sum()
+}
+
+/// Number of integer points strictly inside the polygon, from Pick's theorem.
+pub fn interior_points(corners: &[Point2]) -> i64 {
+    (double_signed_area(corners).abs() - boundary_points(corners) + 2) / 2
+}
+
+/// Number of integer points inside the polygon or on its edges. When the polygon is traced
+/// through the middle of grid cells, this is the number of cells it covers.
+pub fn lattice_points(corners: &[Point2]) -> i64 {
+    interior_points(corners) + boundary_points(corners)
+}
+
+/// Whether `point` is inside, on the edge of or outside the polygon.
+pub fn locate(corners: &[Point2], point: Point2) -> Location {
+    let doubled = corners.iter().map(|&c| c * 2).collect::<Vec<_>>();
+    locate_doubled(&doubled, point * 2)
+}
+
+/// Like [`locate`], but points on the edges count as inside.
+pub fn contains(corners: &[Point2], point: Point2) -> bool {
+    locate(corners, point) != Location::Outside
+}
+
+/// Whether the whole rectangle `rect` is inside the polygon or on its edges. The polygon must
+/// only have horizontal and vertical edges and must not touch itself.
+pub fn contains_rect(corners: &[Point2], rect: &BoundingBox<2>) -> bool {
+    let (min, max) = (rect.min(), rect.max());
+    let doubled = corners.iter().map(|&c| c * 2).collect::<Vec<_>>();
+    if min[0] < max[0] && min[1] < max[1] {
+        // If no edge goes through the inside of the rectangle, the inside is either completely
+        // in or completely out of the polygon, which the middle of the rectangle tells.
+        let crosses = edges(corners).any(|(a, b)| {
+            a[0].min(b[0]) < max[0]
+                && a[0].max(b[0]) > min[0]
+                && a[1].min(b[1]) < max[1]
+                && a[1].max(b[1]) > min[1]
+        });
+        return !crosses && locate_doubled(&doubled, min + max) == Location::Inside;
+    }
+
+    // A line or a point. The corners of the polygon split it into pieces that are each either
+    // completely in or out, so it's enough to look at every split and the middle of every piece.
+    let axis = if min[0] == max[0] { 1 } else { 0 };
+    let mut splits = corners
+        .iter()
+        .map(|c| c[axis])
+        .filter(|&v| min[axis] < v && v < max[axis])
+        .chain([min[axis], max[axis]])
+        .collect::<Vec<_>>();
+    splits.sort_unstable();
+    splits.dedup();
+    let along = |v: i64| {
+        let mut point = min * 2;
+        point[axis] = v;
+        point
+    };
+    splits
+        .iter()
+        .map(|&v| along(v * 2))
+        .chain(splits.windows(2).map(|w| along(w[0] + w[1])))
+        .all(|point| locate_doubled(&doubled, point) != Location::Outside)
+}
+
+fn edges(corners: &[Point2]) -> impl Iterator<Item = (Point2, Point2)> + '_ {
+    corners
+        .iter()
+        .copied()
+        .zip(corners.iter().copied().cycle().skip(1))
+}
+
+/// Counts how many edges a ray going right from `point` crosses. Everything is doubled so that
+/// the middle of a rectangle is still on integer coordinates.
+fn locate_doubled(corners: &[Point2], point: Point2) -> Location {
+    let mut inside = false;
+    for (a, b) in edges(corners) {
+        let (edge, to_point) = (b - a, point - a);
+        let cross = edge[0] * to_point[1] - edge[1] * to_point[0];
+        let between = |axis: usize| {
+            a[axis].min(b[axis]) <= point[axis] && point[axis] <= a[axis].max(b[axis])
+        };
+        if cross == 0 && between(0) && between(1) {
+            return Location::Boundary;
+        }
+        if (a[1] > point[1]) != (b[1] > point[1]) && (cross > 0) == (edge[1] > 0) {
+            inside = !inside;
+        }
+    }
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::point::{BoundingBox, Point2};
+
+    use super::Location;
+
+    fn polygon(corners: &[(i64, i64)]) -> Vec<Point2> {
+        corners.iter().map(|&(x, y)| Point2::new([x, y])).collect()
+    }
+
+    fn rect(a: (i64, i64), b: (i64, i64)) -> BoundingBox<2> {
+        BoundingBox::from_points([Point2::new([a.0, a.1]), Point2::new([b.0, b.1])]).unwrap()
+    }
+
+    /// The red tiles of the example of 2025 day 9, which are the corners of a shape like this:
+    ///
+    /// ```text
+    /// .......#XXX#.
+    /// .......XXXXX.
+    /// ..#XXXX#XXXX.
+    /// ..XXXXXXXXXX.
+    /// ..#XXXXXX#XX.
+    /// .........XXX.
+    /// .........#X#.
+    /// ```
+    fn tiles() -> Vec<Point2> {
+        polygon(&[
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ])
+    }
+
+    #[test]
+    fn area() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(super::double_signed_area(&square), 32);
+        assert_eq!(super::area(&square), 16);
+        assert_eq!(super::boundary_points(&square), 16);
+        assert_eq!(super::interior_points(&square), 9);
+        assert_eq!(super::lattice_points(&square), 25);
+
+        let triangle = polygon(&[(0, 0), (0, 3), (3, 0)]);
+        assert_eq!(super::double_signed_area(&triangle), -9);
+        assert_eq!(super::area(&triangle), 4);
+        assert_eq!(super::boundary_points(&triangle), 9);
+        assert_eq!(super::interior_points(&triangle), 1);
+
+        assert_eq!(super::area(&tiles()), 30);
+        assert_eq!(super::lattice_points(&tiles()), 46);
+    }
+
+    #[test]
+    fn locate() {
+        let tiles = tiles();
+        let at = |x, y| super::locate(&tiles, Point2::new([x, y]));
+        assert_eq!(at(8, 2), Location::Inside);
+        assert_eq!(at(7, 2), Location::Boundary);
+        assert_eq!(at(9, 6), Location::Boundary);
+        assert_eq!(at(11, 4), Location::Boundary);
+        assert_eq!(at(5, 2), Location::Outside);
+        assert_eq!(at(3, 6), Location::Outside);
+        assert_eq!(at(12, 3), Location::Outside);
+        assert!(super::contains(&tiles, Point2::new([2, 4])));
+
+        let triangle = polygon(&[(0, 0), (6, 0), (0, 6)]);
+        assert!(super::contains(&triangle, Point2::new([3, 3])));
+        assert!(!super::contains(&triangle, Point2::new([4, 3])));
+    }
+
+    #[test]
+    fn contains_rect() {
+        let tiles = tiles();
+        assert!(super::contains_rect(&tiles, &rect((9, 5), (2, 3))));
+        assert!(super::contains_rect(&tiles, &rect((7, 1), (11, 5))));
+        assert!(super::contains_rect(&tiles, &rect((9, 7), (11, 1))));
+        assert!(!super::contains_rect(&tiles, &rect((2, 5), (11, 1))));
+        assert!(!super::contains_rect(&tiles, &rect((2, 3), (9, 7))));
+        assert!(!super::contains_rect(&tiles, &rect((4, 2), (8, 4))));
+
+        // Lines and points.
+        assert!(super::contains_rect(&tiles, &rect((2, 4), (11, 4))));
+        assert!(!super::contains_rect(&tiles, &rect((2, 2), (11, 2))));
+        assert!(super::contains_rect(&tiles, &rect((7, 2), (11, 2))));
+        assert!(!super::contains_rect(&tiles, &rect((9, 1), (9, 8))));
+        assert!(super::contains_rect(&tiles, &rect((9, 1), (9, 7))));
+        assert!(super::contains_rect(&tiles, &rect((11, 7), (11, 7))));
+        assert!(!super::contains_rect(&tiles, &rect((0, 0), (0, 0))));
+    }
+}
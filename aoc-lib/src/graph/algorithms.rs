@@ -1,44 +1,478 @@
-use std::collections::VecDeque;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::Add,
+};
 
-use super::DirectedGraph;
+use itertools::Itertools;
 
-pub fn topological_sort<G, T>(mut graph: G) -> Vec<T>
+use crate::shortest_path;
+
+use super::{weighted::Weighted, DirectedGraph};
+
+/// The nodes of a cycle in a graph that was supposed to be acyclic, in the order of the edges.
+/// The last node has an edge back to the first one.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Cycle<T>(pub Vec<T>);
+
+impl<T: Debug> Display for Cycle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Graph has a cycle: ")?;
+        for node in &self.0 {
+            write!(f, "{:?} -> ", node)?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, "{:?}", first),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Debug> std::error::Error for Cycle<T> {}
+
+/// Orders the nodes so that every edge goes from an earlier to a later node. When there's a
+/// choice, nodes come in the order of [`DirectedGraph::nodes`].
+pub fn topological_sort<G, T>(graph: &G) -> Result<Vec<T>, Cycle<T>>
 where
     G: DirectedGraph<T>,
-    T: Eq + Copy + 'static,
+    T: Clone + Eq + Hash + 'static,
 {
-    let mut result = Vec::new();
-    let mut nodes = graph
+    let order = graph
         .nodes()
-        .filter(|&n| graph.incoming_edges(n).count() == 0)
-        .copied()
-        .collect::<VecDeque<_>>();
+        .enumerate()
+        .map(|(i, n)| (n, i))
+        .collect::<HashMap<_, _>>();
+    topological_sort_by(graph, None, |a, b| order[a].cmp(&order[b]))
+}
 
-    while let Some(node) = nodes.pop_front() {
-        result.push(node);
+/// Like [`topological_sort`], but when there's a choice the smallest node according to
+/// `compare` comes first. If `subset` is given only those nodes are sorted, and edges from or to
+/// any other node are ignored.
+pub fn topological_sort_by<G, T, F>(
+    graph: &G,
+    subset: Option<&HashSet<T>>,
+    mut compare: F,
+) -> Result<Vec<T>, Cycle<T>>
+where
+    G: DirectedGraph<T>,
+    T: Clone + Eq + Hash + 'static,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let included = |n: &T| subset.is_none_or(|s| s.contains(n));
+    let mut nodes = match subset {
+        Some(subset) => subset.iter().collect::<Vec<_>>(),
+        None => graph.nodes().collect(),
+    };
+    // Ranking the nodes once lets the heap of ready nodes compare plain numbers.
+    nodes.sort_by(|a, b| compare(a, b));
+    let rank = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, &n)| (n, i))
+        .collect::<HashMap<_, _>>();
+    let mut incoming = nodes
+        .iter()
+        .map(|&n| {
+            let count = graph.incoming_edges(n).filter(|(m, _)| included(m)).count();
+            (n, count)
+        })
+        .collect::<HashMap<_, _>>();
 
-        while let Some((_, m)) = graph.pop_outgoing_edge(&node) {
-            if graph.incoming_edges(&m).count() == 0 {
-                nodes.push_back(m);
+    let mut ready = (0..nodes.len())
+        .filter(|&i| incoming[nodes[i]] == 0)
+        .map(Reverse)
+        .collect::<BinaryHeap<_>>();
+    let mut result = Vec::with_capacity(nodes.len());
+    while let Some(Reverse(i)) = ready.pop() {
+        let node = nodes[i];
+        result.push(node.clone());
+        for (_, next) in graph.outgoing_edges(node) {
+            if let Some(count) = incoming.get_mut(next) {
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(Reverse(rank[next]));
+                }
             }
         }
     }
 
-    if graph.is_empty() {
-        return result;
+    if result.len() == nodes.len() {
+        Ok(result)
     } else {
-        panic!("")
+        Err(find_cycle(graph, &incoming))
+    }
+}
+
+/// Every node that is left after sorting has an incoming edge from another node that is left,
+/// so walking backwards along those edges has to get back to where it has been before.
+fn find_cycle<G, T>(graph: &G, incoming: &HashMap<&T, usize>) -> Cycle<T>
+where
+    G: DirectedGraph<T>,
+    T: Clone + Eq + Hash + 'static,
+{
+    let left = |n: &T| incoming.get(n).is_some_and(|&count| count > 0);
+    let mut node = incoming
+        .iter()
+        .find(|(_, &count)| count > 0)
+        .map(|(&n, _)| n)
+        .expect("Only called when some nodes are left");
+    let mut path = Vec::new();
+    let mut seen = HashMap::new();
+    while !seen.contains_key(node) {
+        seen.insert(node, path.len());
+        path.push(node.clone());
+        node = graph
+            .incoming_edges(node)
+            .map(|(m, _)| m)
+            .find(|m| left(m))
+            .expect("Nodes that are left have a predecessor that is left");
     }
+    let mut cycle = path.split_off(seen[node]);
+    cycle.reverse();
+    Cycle(cycle)
+}
+
+/// Number of edges on the shortest path from `start` to every node that can be reached from it.
+pub fn hop_counts<G, T>(graph: &G, start: &T) -> HashMap<T, usize>
+where
+    G: DirectedGraph<T>,
+    T: Clone + Eq + Hash + 'static,
+{
+    let mut hops = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let count = hops[node];
+        for (_, next) in graph.outgoing_edges(node) {
+            if !hops.contains_key(next) {
+                hops.insert(next.clone(), count + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    hops
+}
+
+/// Length of the shortest path from `start` to every node that can be reached from it. None of
+/// the weights can be negative. This is [`shortest_path::distances`] with the edges of the graph
+/// as successors.
+pub fn distances_from<G, T, W>(graph: &G, start: &T) -> HashMap<T, W>
+where
+    G: Weighted<T, W>,
+    T: Clone + Eq + Hash + 'static,
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    shortest_path::distances([start.clone()], |node| {
+        graph
+            .neighbours(node)
+            .map(|(next, weight)| (next.clone(), weight))
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Like [`distances_from`], but weights can be negative. If a cycle whose weights add up to less than
+/// zero can be reached from `start` there is no shortest path, and that cycle is returned
+/// instead.
+pub fn bellman_ford<G, T, W>(graph: &G, start: &T) -> Result<HashMap<T, W>, Cycle<T>>
+where
+    G: Weighted<T, W>,
+    T: Clone + Eq + Hash + 'static,
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    let edges = graph
+        .edges()
+        .map(|(from, to)| {
+            let weight = graph.weight(from, to).expect("Every edge has a weight");
+            (from, to, weight)
+        })
+        .collect::<Vec<_>>();
+    let node_count = graph.nodes().count();
+
+    // The distance to every node that has been reached, and the node before it on the path.
+    let mut distances = HashMap::from([(start, (W::default(), None))]);
+    let mut rounds = 0;
+    loop {
+        let mut changed = None;
+        for &(from, to, weight) in &edges {
+            let Some(&(distance, _)) = distances.get(from) else {
+                continue;
+            };
+            let next_distance = distance + weight;
+            if distances
+                .get(to)
+                .is_none_or(|&(current, _)| next_distance < current)
+            {
+                distances.insert(to, (next_distance, Some(from)));
+                changed = Some(to);
+            }
+        }
+
+        let Some(mut node) = changed else {
+            return Ok(distances
+                .into_iter()
+                .map(|(node, (distance, _))| (node.clone(), distance))
+                .collect());
+        };
+        rounds += 1;
+        // Without a negative cycle no path needs more edges than there are nodes, so something
+        // that still changes after that many rounds is on, or after, such a cycle. Going back
+        // that many steps is enough to end up on the cycle itself.
+        if rounds >= node_count {
+            let previous = |node: &T| distances[node].1.expect("Nodes on a cycle have a previous");
+            for _ in 0..node_count {
+                node = previous(node);
+            }
+            let mut cycle = vec![node.clone()];
+            let mut current = previous(node);
+            while current != node {
+                cycle.push(current.clone());
+                current = previous(current);
+            }
+            cycle.reverse();
+            return Err(Cycle(cycle));
+        }
+    }
+}
+
+/// The length of the shortest path between every pair of nodes, from [`floyd_warshall`].
+#[derive(Clone, Debug)]
+pub struct AllPairs<T, W> {
+    index: HashMap<T, usize>,
+    distances: Vec<Vec<Option<W>>>,
+}
+
+impl<T, W> AllPairs<T, W>
+where
+    T: Eq + Hash,
+    W: Copy,
+{
+    /// `None` if there is no path from `from` to `to`.
+    pub fn distance(&self, from: &T, to: &T) -> Option<W> {
+        self.distances[*self.index.get(from)?][*self.index.get(to)?]
+    }
+}
+
+/// Shortest paths between all nodes at once. Weights can be negative, as long as they don't add
+/// up to less than zero around a cycle. This takes time proportional to the number of nodes
+/// cubed, so it's only worth it when most of the distances are needed.
+pub fn floyd_warshall<G, T, W>(graph: &G) -> AllPairs<T, W>
+where
+    G: Weighted<T, W>,
+    T: Clone + Eq + Hash + 'static,
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    let index = graph
+        .nodes()
+        .enumerate()
+        .map(|(i, n)| (n.clone(), i))
+        .collect::<HashMap<_, _>>();
+    let n = index.len();
+    let mut distances = vec![vec![None; n]; n];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[i] = Some(W::default());
+    }
+    for (from, to) in graph.edges() {
+        let weight = graph.weight(from, to).expect("Every edge has a weight");
+        let distance = &mut distances[index[from]][index[to]];
+        *distance = Some(distance.map_or(weight, |d: W| d.min(weight)));
+    }
+
+    for k in 0..n {
+        // Going through `k` can't make the distances from `k` shorter, so they can be copied.
+        let from_k = distances[k].clone();
+        for row in distances.iter_mut() {
+            let Some(to_k) = row[k] else {
+                continue;
+            };
+            for (distance, from_k) in row.iter_mut().zip(&from_k) {
+                if let Some(from_k) = from_k {
+                    let through_k = to_k + *from_k;
+                    if distance.is_none_or(|d| through_k < d) {
+                        *distance = Some(through_k);
+                    }
+                }
+            }
+        }
+    }
+
+    AllPairs { index, distances }
+}
+
+/// Why [`count_paths`] couldn't count the paths.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum PathCountError<T> {
+    /// Paths could go around this cycle any number of times.
+    Cycle(Cycle<T>),
+    /// There are more paths than fit in a `u64`.
+    Overflow,
+}
+
+impl<T: Debug> Display for PathCountError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathCountError::Cycle(cycle) => write!(f, "{}", cycle),
+            PathCountError::Overflow => write!(f, "Too many paths to count"),
+        }
+    }
+}
+
+impl<T: Debug> std::error::Error for PathCountError<T> {}
+
+impl<T> From<Cycle<T>> for PathCountError<T> {
+    fn from(cycle: Cycle<T>) -> Self {
+        PathCountError::Cycle(cycle)
+    }
+}
+
+/// Nodes that every path has to go through, for [`count_paths_through`].
+#[derive(Copy, Clone, Debug)]
+pub enum Waypoints<'a, T> {
+    AnyOrder(&'a [T]),
+    InOrder(&'a [T]),
+}
+
+/// Number of different paths from `from` to `to`, where a node on its own is a path to itself.
+/// Only the part of the graph between `from` and `to` has to be acyclic.
+pub fn count_paths<G, T>(graph: &G, from: &T, to: &T) -> Result<u64, PathCountError<T>>
+where
+    G: DirectedGraph<T>,
+    T: Clone + Eq + Hash + 'static,
+{
+    count_paths_through(graph, from, to, Waypoints::InOrder(&[]))
+}
+
+/// Like [`count_paths`], but only counts the paths that go through all of the waypoints. The work
+/// doubles with every waypoint in any order, but only grows linearly with waypoints in order.
+pub fn count_paths_through<G, T>(
+    graph: &G,
+    from: &T,
+    to: &T,
+    waypoints: Waypoints<T>,
+) -> Result<u64, PathCountError<T>>
+where
+    G: DirectedGraph<T>,
+    T: Clone + Eq + Hash + 'static,
+{
+    // Only nodes on some path from `from` to `to` matter, so cycles or huge numbers of paths
+    // anywhere else don't get in the way.
+    let reachable = hop_counts(graph, from).into_keys().collect::<HashSet<_>>();
+    let relevant = reaching(graph, to)
+        .into_iter()
+        .filter(|n| reachable.contains(n))
+        .collect::<HashSet<_>>();
+    if !relevant.contains(from) {
+        return Ok(0);
+    }
+    // `from` is the only node without edges coming in and `to` the only one without edges going
+    // out, so they come first and last, and every path into a node is counted before it's used.
+    let order = topological_sort_by(graph, Some(&relevant), |_, _| Ordering::Equal)?;
+
+    let progress = Progress::new(waypoints);
+    // The number of paths to a node for every state of progress. `None` means more than fit in a
+    // `u64`, which is only an error if it ends up in the answer.
+    let mut counts = HashMap::new();
+    let mut start = vec![Some(0_u64); progress.count()];
+    start[progress.advance(0, from)] = Some(1);
+    counts.insert(from, start);
+    for node in &order {
+        let here = counts
+            .remove(node)
+            .expect("Paths into a node are counted first");
+        if node == to {
+            return here[progress.count() - 1].ok_or(PathCountError::Overflow);
+        }
+        for (_, next) in graph.outgoing_edges(node) {
+            if !relevant.contains(next) {
+                continue;
+            }
+            let there = counts
+                .entry(next)
+                .or_insert_with(|| vec![Some(0); progress.count()]);
+            for (state, &count) in here.iter().enumerate() {
+                let total = &mut there[progress.advance(state, next)];
+                *total = total.zip(count).and_then(|(a, b)| a.checked_add(b));
+            }
+        }
+    }
+    unreachable!("`to` is in the order");
+}
+
+/// How far along the waypoints a path is, as a number below [`Progress::count`]. The last state
+/// means that all of them have been visited.
+enum Progress<'a, T> {
+    /// The set of waypoints visited so far, with one bit for each.
+    Visited(HashMap<&'a T, usize>),
+    /// The number of waypoints passed in order.
+    Passed(&'a [T]),
+}
+
+impl<'a, T: Eq + Hash> Progress<'a, T> {
+    fn new(waypoints: Waypoints<'a, T>) -> Self {
+        match waypoints {
+            Waypoints::AnyOrder(waypoints) => Progress::Visited(
+                waypoints
+                    .iter()
+                    .unique()
+                    .enumerate()
+                    .map(|(i, w)| (w, 1 << i))
+                    .collect(),
+            ),
+            Waypoints::InOrder(waypoints) => Progress::Passed(waypoints),
+        }
+    }
+
+    fn count(&self) -> usize {
+        match self {
+            Progress::Visited(bits) => 1 << bits.len(),
+            Progress::Passed(waypoints) => waypoints.len() + 1,
+        }
+    }
+
+    /// The state after going to `node`.
+    fn advance(&self, mut state: usize, node: &T) -> usize {
+        match self {
+            Progress::Visited(bits) => state | bits.get(node).copied().unwrap_or(0),
+            Progress::Passed(waypoints) => {
+                while waypoints.get(state) == Some(node) {
+                    state += 1;
+                }
+                state
+            }
+        }
+    }
+}
+
+/// Every node that has a path to `to`.
+fn reaching<G, T>(graph: &G, to: &T) -> HashSet<T>
+where
+    G: DirectedGraph<T>,
+    T: Clone + Eq + Hash + 'static,
+{
+    let mut found = HashSet::from([to.clone()]);
+    let mut queue = VecDeque::from([to]);
+    while let Some(node) = queue.pop_front() {
+        for (previous, _) in graph.incoming_edges(node) {
+            if found.insert(previous.clone()) {
+                queue.push_back(previous);
+            }
+        }
+    }
+    found
 }
 
 #[cfg(test)]
 mod test {
-    use crate::graph::AdjacencyListGraph;
+    use std::collections::{HashMap, HashSet};
+
+    use crate::graph::{weighted::WeightedGraph, AdjacencyListGraph};
+
+    use super::{Cycle, PathCountError, Waypoints};
 
     #[test]
     fn topological_sort() {
         let graph = AdjacencyListGraph::new(vec![(5_usize, 11), (11, 2)]);
-        assert_eq!(super::topological_sort(graph), vec![5, 11, 2]);
+        assert_eq!(super::topological_sort(&graph), Ok(vec![5, 11, 2]));
 
         let graph = AdjacencyListGraph::new(vec![
             (5, 11),
@@ -51,11 +485,11 @@ mod test {
             (3, 8),
             (3, 10),
         ]);
-        let sort = super::topological_sort(graph);
+        let sort = super::topological_sort(&graph).unwrap();
 
         assert!(is_valid(
             &sort,
-            &vec![
+            &[
                 (5, 11),
                 (11, 2),
                 (7, 11),
@@ -67,9 +501,262 @@ mod test {
                 (3, 10)
             ]
         ));
+        assert_eq!(sort, [5, 7, 11, 2, 3, 8, 9, 10]);
+    }
+
+    #[test]
+    fn alphabetical() {
+        // The example of 2018 day 7.
+        let graph = AdjacencyListGraph::new(vec![
+            ('C', 'A'),
+            ('C', 'F'),
+            ('A', 'B'),
+            ('A', 'D'),
+            ('B', 'E'),
+            ('D', 'E'),
+            ('F', 'E'),
+        ]);
+        let sort = super::topological_sort_by(&graph, None, |a, b| a.cmp(b)).unwrap();
+        assert_eq!(sort.into_iter().collect::<String>(), "CABDFE");
+
+        let subset = HashSet::from(['A', 'E', 'F']);
+        let sort = super::topological_sort_by(&graph, Some(&subset), |a, b| b.cmp(a)).unwrap();
+        assert_eq!(sort.into_iter().collect::<String>(), "FEA");
+    }
+
+    #[test]
+    fn cycles() {
+        let graph = AdjacencyListGraph::new(vec![(1, 2), (2, 3), (3, 4), (4, 2), (4, 5)]);
+        let Err(Cycle(cycle)) = super::topological_sort(&graph) else {
+            panic!("Should have found a cycle");
+        };
+        assert_eq!(cycle.len(), 3);
+        let start = cycle.iter().position(|&n| n == 2).unwrap();
+        assert_eq!(
+            cycle[start..]
+                .iter()
+                .chain(&cycle[..start])
+                .collect::<Vec<_>>(),
+            [&2, &3, &4]
+        );
+
+        // Without node 4 the cycle is gone.
+        let subset = HashSet::from([1, 2, 3, 5]);
+        assert_eq!(
+            super::topological_sort_by(&graph, Some(&subset), |a, b| a.cmp(b)),
+            Ok(vec![1, 2, 3, 5])
+        );
+
+        let graph = AdjacencyListGraph::new(vec![(1, 1)]);
+        assert_eq!(super::topological_sort(&graph), Err(Cycle(vec![1])));
+        assert_eq!(
+            Cycle(vec![1, 2]).to_string(),
+            "Graph has a cycle: 1 -> 2 -> 1"
+        );
+    }
+
+    #[test]
+    fn hop_counts() {
+        // The orbits of 2019 day 6, where going from YOU to SAN takes 4 orbital transfers.
+        let orbits = [
+            ("COM", "B"),
+            ("B", "C"),
+            ("C", "D"),
+            ("D", "E"),
+            ("E", "F"),
+            ("B", "G"),
+            ("G", "H"),
+            ("D", "I"),
+            ("E", "J"),
+            ("J", "K"),
+            ("K", "L"),
+            ("K", "YOU"),
+            ("I", "SAN"),
+        ];
+        let graph = AdjacencyListGraph::new(orbits);
+        let hops = super::hop_counts(&graph, &"COM");
+        assert_eq!(hops.values().sum::<usize>(), 54);
+        assert_eq!(super::hop_counts(&graph, &"SAN").len(), 1);
+
+        let graph = AdjacencyListGraph::new(orbits.iter().flat_map(|&(a, b)| [(a, b), (b, a)]));
+        assert_eq!(super::hop_counts(&graph, &"YOU")[&"SAN"] - 2, 4);
+    }
+
+    fn weighted() -> WeightedGraph<char, i32> {
+        WeightedGraph::directed([
+            ('a', 'b', 4),
+            ('a', 'c', 1),
+            ('c', 'b', 2),
+            ('b', 'd', 1),
+            ('c', 'd', 5),
+            ('e', 'a', 1),
+        ])
+    }
+
+    #[test]
+    fn distances_from() {
+        let distances = super::distances_from(&weighted(), &'a');
+        assert_eq!(
+            distances,
+            HashMap::from([('a', 0), ('b', 3), ('c', 1), ('d', 4)])
+        );
+
+        let cities = WeightedGraph::undirected([
+            ("London", "Dublin", 464),
+            ("London", "Belfast", 518),
+            ("Dublin", "Belfast", 141),
+        ]);
+        assert_eq!(super::distances_from(&cities, &"Belfast")[&"London"], 518);
+    }
+
+    #[test]
+    fn bellman_ford() {
+        let mut graph = weighted();
+        graph.add_edge('b', 'c', -3);
+        graph.add_edge('c', 'b', 4);
+        assert_eq!(
+            super::bellman_ford(&graph, &'a'),
+            Ok(HashMap::from([('a', 0), ('b', 4), ('c', 1), ('d', 5)]))
+        );
+        assert_eq!(
+            super::bellman_ford(&graph, &'e'),
+            Ok(HashMap::from([
+                ('a', 1),
+                ('b', 5),
+                ('c', 2),
+                ('d', 6),
+                ('e', 0)
+            ]))
+        );
+
+        graph.add_edge('c', 'b', 2);
+        let Err(Cycle(mut cycle)) = super::bellman_ford(&graph, &'a') else {
+            panic!("Should have found a negative cycle");
+        };
+        cycle.sort();
+        assert_eq!(cycle, ['b', 'c']);
+        assert!(super::bellman_ford(&graph, &'d').is_ok());
+    }
+
+    #[test]
+    fn floyd_warshall() {
+        let mut graph = weighted();
+        let distances = super::floyd_warshall(&graph);
+        assert_eq!(distances.distance(&'e', &'d'), Some(5));
+        assert_eq!(distances.distance(&'c', &'c'), Some(0));
+        assert_eq!(distances.distance(&'d', &'a'), None);
+        assert_eq!(distances.distance(&'x', &'a'), None);
+
+        graph.add_edge('d', 'e', -2);
+        let distances = super::floyd_warshall(&graph);
+        assert_eq!(distances.distance(&'d', &'b'), Some(2));
+        for from in "abcde".chars() {
+            let single_source = super::bellman_ford(&graph, &from).unwrap();
+            for to in "abcde".chars() {
+                assert_eq!(
+                    distances.distance(&from, &to),
+                    single_source.get(&to).copied()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn count_paths() {
+        // The examples of 2025 day 11.
+        let graph = AdjacencyListGraph::new([
+            ("aaa", "you"),
+            ("aaa", "hhh"),
+            ("you", "bbb"),
+            ("you", "ccc"),
+            ("bbb", "ddd"),
+            ("bbb", "eee"),
+            ("ccc", "ddd"),
+            ("ccc", "eee"),
+            ("ccc", "fff"),
+            ("ddd", "ggg"),
+            ("eee", "out"),
+            ("fff", "out"),
+            ("ggg", "out"),
+            ("hhh", "ccc"),
+            ("hhh", "fff"),
+            ("hhh", "iii"),
+            ("iii", "out"),
+        ]);
+        assert_eq!(super::count_paths(&graph, &"you", &"out"), Ok(5));
+        assert_eq!(super::count_paths(&graph, &"you", &"you"), Ok(1));
+        assert_eq!(super::count_paths(&graph, &"out", &"you"), Ok(0));
+
+        let graph = AdjacencyListGraph::new([
+            ("svr", "aaa"),
+            ("svr", "bbb"),
+            ("aaa", "fft"),
+            ("fft", "ccc"),
+            ("bbb", "tty"),
+            ("tty", "ccc"),
+            ("ccc", "ddd"),
+            ("ccc", "eee"),
+            ("ddd", "hub"),
+            ("hub", "fff"),
+            ("eee", "dac"),
+            ("dac", "fff"),
+            ("fff", "ggg"),
+            ("fff", "hhh"),
+            ("ggg", "out"),
+            ("hhh", "out"),
+        ]);
+        let count = |waypoints| super::count_paths_through(&graph, &"svr", &"out", waypoints);
+        assert_eq!(super::count_paths(&graph, &"svr", &"out"), Ok(8));
+        assert_eq!(count(Waypoints::AnyOrder(&["dac", "fft"])), Ok(2));
+        assert_eq!(count(Waypoints::AnyOrder(&["dac", "fft", "dac"])), Ok(2));
+        assert_eq!(count(Waypoints::InOrder(&["fft", "dac"])), Ok(2));
+        assert_eq!(count(Waypoints::InOrder(&["dac", "fft"])), Ok(0));
+        assert_eq!(count(Waypoints::AnyOrder(&["ccc", "svr"])), Ok(8));
+    }
+
+    #[test]
+    fn count_paths_errors() {
+        // Every diamond doubles the number of paths.
+        let diamonds = |count: usize| {
+            AdjacencyListGraph::new((0..count).flat_map(|i| {
+                let (start, end) = (3 * i, 3 * i + 3);
+                [
+                    (start, start + 1),
+                    (start, start + 2),
+                    (start + 1, end),
+                    (start + 2, end),
+                ]
+            }))
+        };
+        assert_eq!(super::count_paths(&diamonds(63), &0, &189), Ok(1 << 63));
+        assert_eq!(
+            super::count_paths(&diamonds(64), &0, &192),
+            Err(PathCountError::Overflow)
+        );
+
+        // Paths that never get to the end, or that miss a waypoint, don't count.
+        let mut graph = diamonds(64);
+        graph.add_edge(0, 1000);
+        assert_eq!(super::count_paths(&graph, &0, &1000), Ok(1));
+        graph.add_edge(1000, 192);
+        assert_eq!(
+            super::count_paths(&graph, &0, &192),
+            Err(PathCountError::Overflow)
+        );
+        let count = |waypoints| super::count_paths_through(&graph, &0, &192, waypoints);
+        assert_eq!(count(Waypoints::AnyOrder(&[1000])), Ok(1));
+        assert_eq!(count(Waypoints::InOrder(&[0, 1000, 192])), Ok(1));
+
+        let mut graph = diamonds(2);
+        graph.add_edge(2, 0);
+        assert_eq!(super::count_paths(&graph, &3, &6), Ok(2));
+        assert!(matches!(
+            super::count_paths(&graph, &0, &6),
+            Err(PathCountError::Cycle(_))
+        ));
     }
 
-    fn is_valid(update: &Vec<usize>, rules: &Vec<(usize, usize)>) -> bool {
+    fn is_valid(update: &[usize], rules: &[(usize, usize)]) -> bool {
         for (i, x) in update.iter().enumerate() {
             for other in &update[i + 1..] {
                 if rules.iter().any(|(r1, r2)| r1 == other && r2 == x) {
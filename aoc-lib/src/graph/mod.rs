@@ -1,8 +1,7 @@
-use std::hash::Hash;
-
-use itertools::Itertools;
+use std::{collections::HashMap, hash::Hash};
 
 pub mod algorithms;
+pub mod weighted;
 
 pub trait DirectedGraph<T> {
     fn edges(&self) -> impl Iterator<Item = &(T, T)>
@@ -24,35 +23,181 @@ pub trait DirectedGraph<T> {
     where
         T: Eq;
 
+    /// Whether the graph has no edges left. It can still have nodes.
     fn is_empty(&self) -> bool;
 }
 
-#[derive(Debug)]
+/// A directed graph without parallel edges. Every node has a list of the edges going out of it
+/// and one of the edges coming into it, so looking up the neighbours of a node is as fast as
+/// adding or removing an edge. Nodes stay in the graph when their edges are removed.
+#[derive(Clone, Debug)]
 pub struct AdjacencyListGraph<T> {
-    data: Vec<(T, T)>,
+    edges: Vec<(T, T)>,
+    links: Vec<Link>,
+    edge_ids: HashMap<(usize, usize), usize>,
     nodes: Vec<T>,
+    node_ids: HashMap<T, usize>,
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+}
+
+/// Where an edge is in the adjacency lists. The edge with the same index in `edges` goes from
+/// node `from` to node `to`, and is at `outgoing[from][out_pos]` and `incoming[to][in_pos]`.
+#[derive(Copy, Clone, Debug)]
+struct Link {
+    from: usize,
+    to: usize,
+    out_pos: usize,
+    in_pos: usize,
 }
 
 impl<T> AdjacencyListGraph<T>
 where
     T: Clone + Eq + Hash,
 {
-    pub fn new(edges: Vec<(T, T)>) -> Self {
-        let nodes = edges
-            .iter()
-            .flat_map(|(a, b)| [a.clone(), b.clone()].into_iter())
-            .unique()
-            .collect::<Vec<_>>();
-        AdjacencyListGraph { data: edges, nodes }
+    pub fn new<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = (T, T)>,
+    {
+        let mut graph = Self {
+            edges: Vec::new(),
+            links: Vec::new(),
+            edge_ids: HashMap::new(),
+            nodes: Vec::new(),
+            node_ids: HashMap::new(),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        };
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn contains_node(&self, node: &T) -> bool {
+        self.node_ids.contains_key(node)
+    }
+
+    pub fn contains_edge(&self, from: &T, to: &T) -> bool {
+        self.edge_id(from, to).is_some()
+    }
+
+    /// Nodes are numbered from 0 in the order they were added, and keep their index even when
+    /// all of their edges are removed.
+    pub fn index_of(&self, node: &T) -> Option<usize> {
+        self.node_ids.get(node).copied()
+    }
+
+    pub fn node(&self, index: usize) -> &T {
+        &self.nodes[index]
+    }
+
+    /// Adds a node without any edges. Returns `false` if it was already in the graph.
+    pub fn add_node(&mut self, node: T) -> bool {
+        let count = self.nodes.len();
+        self.node_id(node) == count
+    }
+
+    /// Adds an edge, and its nodes if they aren't in the graph yet. Returns `false` if the edge
+    /// was already there.
+    pub fn add_edge(&mut self, from: T, to: T) -> bool {
+        let (from_id, to_id) = (self.node_id(from.clone()), self.node_id(to.clone()));
+        if self.edge_ids.contains_key(&(from_id, to_id)) {
+            return false;
+        }
+        let id = self.edges.len();
+        self.edges.push((from, to));
+        self.links.push(Link {
+            from: from_id,
+            to: to_id,
+            out_pos: self.outgoing[from_id].len(),
+            in_pos: self.incoming[to_id].len(),
+        });
+        self.outgoing[from_id].push(id);
+        self.incoming[to_id].push(id);
+        self.edge_ids.insert((from_id, to_id), id);
+        true
+    }
+
+    /// Returns `false` if there was no such edge.
+    pub fn remove_edge(&mut self, from: &T, to: &T) -> bool {
+        match self.edge_id(from, to) {
+            Some(id) => {
+                self.remove_edge_id(id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn node_id(&mut self, node: T) -> usize {
+        if let Some(&id) = self.node_ids.get(&node) {
+            return id;
+        }
+        let id = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.node_ids.insert(node, id);
+        self.outgoing.push(Vec::new());
+        self.incoming.push(Vec::new());
+        id
+    }
+
+    fn edge_id(&self, from: &T, to: &T) -> Option<usize> {
+        let key = (*self.node_ids.get(from)?, *self.node_ids.get(to)?);
+        self.edge_ids.get(&key).copied()
+    }
+
+    /// Removes the edge by swapping the last one into its place, here and in the adjacency lists,
+    /// so nothing has to be shifted.
+    fn remove_edge_id(&mut self, id: usize) -> (T, T) {
+        let link = self.links[id];
+        self.outgoing[link.from].swap_remove(link.out_pos);
+        if let Some(&moved) = self.outgoing[link.from].get(link.out_pos) {
+            self.links[moved].out_pos = link.out_pos;
+        }
+        self.incoming[link.to].swap_remove(link.in_pos);
+        if let Some(&moved) = self.incoming[link.to].get(link.in_pos) {
+            self.links[moved].in_pos = link.in_pos;
+        }
+        self.edge_ids.remove(&(link.from, link.to));
+
+        let edge = self.edges.swap_remove(id);
+        self.links.swap_remove(id);
+        if let Some(&moved) = self.links.get(id) {
+            self.outgoing[moved.from][moved.out_pos] = id;
+            self.incoming[moved.to][moved.in_pos] = id;
+            self.edge_ids.insert((moved.from, moved.to), id);
+        }
+        edge
     }
 }
 
-impl<T> DirectedGraph<T> for AdjacencyListGraph<T> {
+impl<T> Default for AdjacencyListGraph<T>
+where
+    T: Clone + Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new([])
+    }
+}
+
+impl<T> DirectedGraph<T> for AdjacencyListGraph<T>
+where
+    T: Clone + Eq + Hash,
+{
     fn edges(&self) -> impl Iterator<Item = &(T, T)>
     where
         T: 'static,
     {
-        self.data.iter()
+        self.edges.iter()
     }
 
     fn nodes(&self) -> impl Iterator<Item = &T>
@@ -66,27 +211,119 @@ impl<T> DirectedGraph<T> for AdjacencyListGraph<T> {
     where
         T: Eq + 'static,
     {
-        self.data.iter().filter(move |(_, b)| b == node)
+        self.node_ids
+            .get(node)
+            .into_iter()
+            .flat_map(|&id| self.incoming[id].iter().map(|&e| &self.edges[e]))
     }
 
-    fn outgoing_edges(&self, _node: &T) -> impl Iterator<Item = &(T, T)>
+    fn outgoing_edges(&self, node: &T) -> impl Iterator<Item = &(T, T)>
     where
         T: Eq + 'static,
     {
-        Vec::new().into_iter()
+        self.node_ids
+            .get(node)
+            .into_iter()
+            .flat_map(|&id| self.outgoing[id].iter().map(|&e| &self.edges[e]))
     }
 
     fn pop_outgoing_edge(&mut self, node: &T) -> Option<(T, T)>
     where
         T: Eq,
     {
-        if let Some((index, _)) = self.data.iter().enumerate().find(|(_, (n, _))| n == node) {
-            return Some(self.data.remove(index));
-        }
-        None
+        let id = *self.outgoing[*self.node_ids.get(node)?].last()?;
+        Some(self.remove_edge_id(id))
     }
 
     fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.edges.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AdjacencyListGraph, DirectedGraph};
+
+    fn graph() -> AdjacencyListGraph<&'static str> {
+        AdjacencyListGraph::new([("a", "b"), ("a", "c"), ("b", "c"), ("c", "d"), ("a", "b")])
+    }
+
+    fn sorted<'a>(edges: impl Iterator<Item = &'a (&'static str, &'static str)>) -> Vec<String> {
+        let mut edges = edges
+            .map(|(a, b)| format!("{}{}", a, b))
+            .collect::<Vec<_>>();
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn edges_and_nodes() {
+        let graph = graph();
+        assert_eq!(sorted(graph.edges()), ["ab", "ac", "bc", "cd"]);
+        assert_eq!(
+            graph.nodes().copied().collect::<Vec<_>>(),
+            ["a", "b", "c", "d"]
+        );
+        assert_eq!((graph.node_count(), graph.edge_count()), (4, 4));
+        assert!(graph.contains_edge(&"a", &"c"));
+        assert_eq!(graph.index_of(&"c"), Some(2));
+        assert_eq!(graph.index_of(&"x"), None);
+        assert_eq!(graph.node(3), &"d");
+        assert!(!graph.contains_edge(&"c", &"a"));
+        assert!(!graph.contains_edge(&"a", &"x"));
+        assert!(!graph.is_empty());
+        assert!(AdjacencyListGraph::<u32>::default().is_empty());
+    }
+
+    #[test]
+    fn incoming_and_outgoing() {
+        let graph = graph();
+        assert_eq!(sorted(graph.outgoing_edges(&"a")), ["ab", "ac"]);
+        assert_eq!(sorted(graph.outgoing_edges(&"d")), Vec::<String>::new());
+        assert_eq!(sorted(graph.outgoing_edges(&"x")), Vec::<String>::new());
+        assert_eq!(sorted(graph.incoming_edges(&"c")), ["ac", "bc"]);
+        assert_eq!(sorted(graph.incoming_edges(&"a")), Vec::<String>::new());
+    }
+
+    #[test]
+    fn pop_outgoing_edge() {
+        let mut graph = graph();
+        let mut popped = Vec::new();
+        while let Some(edge) = graph.pop_outgoing_edge(&"a") {
+            popped.push(edge);
+        }
+        popped.sort();
+        assert_eq!(popped, [("a", "b"), ("a", "c")]);
+        assert_eq!(sorted(graph.edges()), ["bc", "cd"]);
+        assert_eq!(sorted(graph.incoming_edges(&"c")), ["bc"]);
+        assert_eq!(graph.pop_outgoing_edge(&"x"), None);
+
+        graph.pop_outgoing_edge(&"b");
+        graph.pop_outgoing_edge(&"c");
+        assert!(graph.is_empty());
+        assert_eq!(graph.node_count(), 4);
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let mut graph = graph();
+        assert!(graph.remove_edge(&"a", &"b"));
+        assert!(!graph.remove_edge(&"a", &"b"));
+        assert!(graph.add_edge("d", "a"));
+        assert!(!graph.add_edge("d", "a"));
+        assert!(graph.add_node("e"));
+        assert!(!graph.add_node("a"));
+        assert!(graph.remove_edge(&"a", &"c"));
+
+        assert_eq!(sorted(graph.edges()), ["bc", "cd", "da"]);
+        assert_eq!(sorted(graph.outgoing_edges(&"a")), Vec::<String>::new());
+        assert_eq!(sorted(graph.incoming_edges(&"a")), ["da"]);
+        assert_eq!(sorted(graph.incoming_edges(&"c")), ["bc"]);
+        assert_eq!(sorted(graph.outgoing_edges(&"d")), ["da"]);
+        assert!(graph.contains_node(&"e"));
+
+        let copy = graph.clone();
+        graph.remove_edge(&"d", &"a");
+        assert_eq!(sorted(copy.edges()), ["bc", "cd", "da"]);
     }
 }
@@ -0,0 +1,248 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::{AdjacencyListGraph, DirectedGraph};
+
+/// A directed graph with a weight on every edge.
+pub trait Weighted<T, W>: DirectedGraph<T> {
+    fn weight(&self, from: &T, to: &T) -> Option<W>;
+
+    /// The nodes that `node` has an edge to, together with the weight of that edge.
+    fn neighbours(&self, node: &T) -> impl Iterator<Item = (&T, W)>
+    where
+        T: Eq + 'static,
+    {
+        self.outgoing_edges(node).map(|(from, to)| {
+            let weight = self.weight(from, to).expect("Every edge has a weight");
+            (to, weight)
+        })
+    }
+}
+
+/// A graph with a weight on every edge, that can be directed or undirected. An undirected graph
+/// has every edge in both directions, with the same weight, so it works as a [`DirectedGraph`]
+/// too. Nodes are numbered the same way as in an [`AdjacencyListGraph`].
+#[derive(Clone, Debug)]
+pub struct WeightedGraph<T, W> {
+    graph: AdjacencyListGraph<T>,
+    weights: HashMap<(usize, usize), W>,
+    undirected: bool,
+}
+
+impl<T, W> WeightedGraph<T, W>
+where
+    T: Clone + Eq + Hash,
+    W: Copy,
+{
+    pub fn directed<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = (T, T, W)>,
+    {
+        Self::with_edges(edges, false)
+    }
+
+    pub fn undirected<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = (T, T, W)>,
+    {
+        Self::with_edges(edges, true)
+    }
+
+    fn with_edges<I>(edges: I, undirected: bool) -> Self
+    where
+        I: IntoIterator<Item = (T, T, W)>,
+    {
+        let mut graph = Self {
+            graph: AdjacencyListGraph::default(),
+            weights: HashMap::new(),
+            undirected,
+        };
+        for (from, to, weight) in edges {
+            graph.add_edge(from, to, weight);
+        }
+        graph
+    }
+
+    pub fn is_undirected(&self) -> bool {
+        self.undirected
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Number of edges, where an undirected edge counts once in each direction.
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    pub fn contains_node(&self, node: &T) -> bool {
+        self.graph.contains_node(node)
+    }
+
+    pub fn contains_edge(&self, from: &T, to: &T) -> bool {
+        self.graph.contains_edge(from, to)
+    }
+
+    /// See [`AdjacencyListGraph::index_of`].
+    pub fn index_of(&self, node: &T) -> Option<usize> {
+        self.graph.index_of(node)
+    }
+
+    pub fn node(&self, index: usize) -> &T {
+        self.graph.node(index)
+    }
+
+    /// Adds a node without any edges. Returns `false` if it was already in the graph.
+    pub fn add_node(&mut self, node: T) -> bool {
+        self.graph.add_node(node)
+    }
+
+    /// Adds an edge, and its nodes if they aren't in the graph yet. If the edge was already
+    /// there its weight is replaced, and the old weight is returned.
+    pub fn add_edge(&mut self, from: T, to: T, weight: W) -> Option<W> {
+        if self.undirected && from != to {
+            self.insert_edge(to.clone(), from.clone(), weight);
+        }
+        self.insert_edge(from, to, weight)
+    }
+
+    /// Returns the weight of the removed edge, or `None` if there was no such edge.
+    pub fn remove_edge(&mut self, from: &T, to: &T) -> Option<W> {
+        let key = self.key(from, to)?;
+        if self.undirected && from != to {
+            self.graph.remove_edge(to, from);
+            self.weights.remove(&(key.1, key.0));
+        }
+        self.graph.remove_edge(from, to);
+        self.weights.remove(&key)
+    }
+
+    fn insert_edge(&mut self, from: T, to: T, weight: W) -> Option<W> {
+        self.graph.add_edge(from.clone(), to.clone());
+        let key = self.key(&from, &to).expect("The edge was just added");
+        self.weights.insert(key, weight)
+    }
+
+    fn key(&self, from: &T, to: &T) -> Option<(usize, usize)> {
+        Some((self.graph.index_of(from)?, self.graph.index_of(to)?))
+    }
+}
+
+impl<T, W> DirectedGraph<T> for WeightedGraph<T, W>
+where
+    T: Clone + Eq + Hash,
+    W: Copy,
+{
+    fn edges(&self) -> impl Iterator<Item = &(T, T)>
+    where
+        T: 'static,
+    {
+        self.graph.edges()
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = &T>
+    where
+        T: 'static,
+    {
+        self.graph.nodes()
+    }
+
+    fn incoming_edges(&self, node: &T) -> impl Iterator<Item = &(T, T)>
+    where
+        T: Eq + 'static,
+    {
+        self.graph.incoming_edges(node)
+    }
+
+    fn outgoing_edges(&self, node: &T) -> impl Iterator<Item = &(T, T)>
+    where
+        T: Eq + 'static,
+    {
+        self.graph.outgoing_edges(node)
+    }
+
+    /// In an undirected graph the edge is removed in both directions.
+    fn pop_outgoing_edge(&mut self, node: &T) -> Option<(T, T)>
+    where
+        T: Eq,
+    {
+        let (from, to) = self.graph.pop_outgoing_edge(node)?;
+        let key = self.key(&from, &to).expect("Nodes are never removed");
+        self.weights.remove(&key);
+        if self.undirected && from != to {
+            self.graph.remove_edge(&to, &from);
+            self.weights.remove(&(key.1, key.0));
+        }
+        Some((from, to))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.graph.is_empty()
+    }
+}
+
+impl<T, W> Weighted<T, W> for WeightedGraph<T, W>
+where
+    T: Clone + Eq + Hash,
+    W: Copy,
+{
+    fn weight(&self, from: &T, to: &T) -> Option<W> {
+        self.weights.get(&self.key(from, to)?).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::graph::DirectedGraph;
+
+    use super::{Weighted, WeightedGraph};
+
+    #[test]
+    fn directed() {
+        let mut graph = WeightedGraph::directed([("a", "b", 3), ("b", "c", 4), ("a", "c", 10)]);
+        assert_eq!(graph.weight(&"a", &"b"), Some(3));
+        assert_eq!(graph.weight(&"b", &"a"), None);
+        assert_eq!(graph.add_edge("a", "c", 5), Some(10));
+        assert_eq!(graph.add_edge("c", "a", 1), None);
+
+        let mut neighbours = graph.neighbours(&"a").collect::<Vec<_>>();
+        neighbours.sort();
+        assert_eq!(neighbours, [(&"b", 3), (&"c", 5)]);
+        assert_eq!((graph.node_count(), graph.edge_count()), (3, 4));
+        assert_eq!(graph.index_of(&"c"), Some(2));
+        assert_eq!(graph.node(1), &"b");
+
+        assert_eq!(graph.remove_edge(&"a", &"c"), Some(5));
+        assert_eq!(graph.remove_edge(&"a", &"c"), None);
+        assert_eq!(graph.weight(&"a", &"c"), None);
+        assert!(graph.contains_edge(&"c", &"a"));
+    }
+
+    #[test]
+    fn undirected() {
+        let mut graph = WeightedGraph::undirected([
+            ("London", "Dublin", 464),
+            ("London", "Belfast", 518),
+            ("Dublin", "Belfast", 141),
+        ]);
+        assert!(graph.is_undirected());
+        assert_eq!(graph.edge_count(), 6);
+        assert_eq!(graph.weight(&"Belfast", &"London"), Some(518));
+        assert_eq!(graph.incoming_edges(&"Dublin").count(), 2);
+
+        assert_eq!(graph.remove_edge(&"Dublin", &"London"), Some(464));
+        assert_eq!(graph.weight(&"London", &"Dublin"), None);
+        assert!(graph.pop_outgoing_edge(&"Belfast").is_some());
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.outgoing_edges(&"Belfast").count(), 1);
+        assert!(graph.contains_node(&"London"));
+
+        graph.add_edge("Paris", "Paris", 0);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.remove_edge(&"Paris", &"Paris"), Some(0));
+        assert_eq!(graph.edge_count(), 2);
+        graph.add_edge("Paris", "Paris", 0);
+        assert_eq!(graph.pop_outgoing_edge(&"Paris"), Some(("Paris", "Paris")));
+        assert_eq!(graph.weight(&"Paris", &"Paris"), None);
+    }
+}
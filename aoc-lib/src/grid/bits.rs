@@ -0,0 +1,467 @@
+use std::{
+    fmt::Debug,
+    ops::{BitAnd, BitOr, BitXor, Not},
+};
+
+use super::{Direction, Grid, Pos};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A grid of booleans that stores one bit per cell, 64 cells to a word. Operations on the
+/// whole grid (shifts, AND/OR, counting neighbours) work on entire words at a time.
+///
+/// Bits past the last col of a row are always 0, so two grids with the same cells are equal and
+/// hash the same.
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct BitGrid {
+    rows: usize,
+    cols: usize,
+    words: usize,
+    data: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid with every cell off.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let words = cols.div_ceil(BITS);
+        Self {
+            rows,
+            cols,
+            words,
+            data: vec![0; rows * words],
+        }
+    }
+
+    pub fn from_grid(grid: &Grid<bool>) -> Self {
+        let mut bits = Self::new(grid.rows(), grid.cols());
+        for point in grid.iter() {
+            bits.set(point.pos, point.value);
+        }
+        bits
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::from_rows(
+            (0..self.rows)
+                .map(|row| (0..self.cols).map(|col| self.get(Pos(row, col))).collect())
+                .collect(),
+        )
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row() < self.rows && pos.col() < self.cols
+    }
+
+    /// `false` for every position outside of the grid.
+    pub fn get(&self, pos: Pos) -> bool {
+        self.contains(pos) && {
+            let (word, bit) = self.index(pos);
+            self.data[word] >> bit & 1 == 1
+        }
+    }
+
+    /// Panics if `pos` is outside of the grid.
+    pub fn set(&mut self, pos: Pos, value: bool) {
+        self.check_bounds(pos);
+        let (word, bit) = self.index(pos);
+        if value {
+            self.data[word] |= 1 << bit;
+        } else {
+            self.data[word] &= !(1 << bit);
+        }
+    }
+
+    /// Number of cells that are on.
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.iter().all(|&w| w == 0)
+    }
+
+    /// Positions of all cells that are on, row by row.
+    pub fn ones(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.rows).flat_map(move |row| {
+            self.row_words(row)
+                .iter()
+                .enumerate()
+                .flat_map(move |(i, &word)| {
+                    std::iter::successors(Some(word), |&w| Some(w & w.wrapping_sub(1)))
+                        .take_while(|&w| w != 0)
+                        .map(move |w| Pos(row, i * BITS + w.trailing_zeros() as usize))
+                })
+        })
+    }
+
+    /// The cells of `row` as bits, col 0 being the least significant bit. Panics if the grid
+    /// is wider than 64 cols.
+    pub fn row_bits(&self, row: usize) -> u64 {
+        assert!(
+            self.cols <= BITS,
+            "Rows of {} cols don't fit in a u64",
+            self.cols
+        );
+        self.row_words(row).first().copied().unwrap_or(0)
+    }
+
+    /// Sets the cells of `row` from bits, col 0 being the least significant bit. Bits past the
+    /// last col are ignored.
+    pub fn set_row_bits(&mut self, row: usize, bits: u64) {
+        assert!(
+            self.cols <= BITS,
+            "Rows of {} cols don't fit in a u64",
+            self.cols
+        );
+        assert!(
+            row < self.rows,
+            "Row {} is outside of grid with {} rows",
+            row,
+            self.rows
+        );
+        if self.words > 0 {
+            self.data[row * self.words] = bits & self.last_word_mask();
+        }
+    }
+
+    /// Adds `count` empty rows at the bottom, e.g. to make room for a falling block.
+    pub fn push_rows(&mut self, count: usize) {
+        self.rows += count;
+        self.data.resize(self.rows * self.words, 0);
+    }
+
+    /// Whether any cell is on in both grids.
+    pub fn intersects(&self, other: &BitGrid) -> bool {
+        self.check_same_size(other);
+        self.data.iter().zip(&other.data).any(|(a, b)| a & b != 0)
+    }
+
+    /// Every cell moved one step in direction `dir`. Cells that move off the grid are lost and
+    /// the cells they leave behind are off.
+    pub fn shifted(&self, dir: Direction) -> BitGrid {
+        let (row_step, col_step) = dir.delta();
+        self.shifted_by(row_step, col_step)
+    }
+
+    /// Every cell moved by `row_step` rows and `col_step` cols.
+    pub fn shifted_by(&self, row_step: isize, col_step: isize) -> BitGrid {
+        let mut shifted = Self::new(self.rows, self.cols);
+        for row in 0..self.rows {
+            let Some(target) = row.checked_add_signed(row_step).filter(|&r| r < self.rows) else {
+                continue;
+            };
+            let source = self.row_words(row);
+            let dest = &mut shifted.data[target * self.words..(target + 1) * self.words];
+            shift_words(source, dest, col_step);
+        }
+        shifted.clear_padding();
+        shifted
+    }
+
+    /// For every cell, whether the number of neighbours that are on is one of `counts`. The
+    /// counting is done with bitwise adders on whole words, so it's a lot faster than looking at
+    /// neighbours one cell at a time.
+    pub fn neighbours_in(&self, directions: &[Direction], counts: &[u32]) -> BitGrid {
+        // Bit i of the neighbour count of every cell, as a binary counter spread over 4 grids.
+        let mut planes = vec![vec![0u64; self.data.len()]; 4];
+        for &dir in directions {
+            let shifted = self.shifted(dir.opposite());
+            for (i, &word) in shifted.data.iter().enumerate() {
+                let mut carry = word;
+                for plane in planes.iter_mut() {
+                    let sum = plane[i] ^ carry;
+                    carry &= plane[i];
+                    plane[i] = sum;
+                }
+            }
+        }
+
+        let mut result = Self::new(self.rows, self.cols);
+        for (i, word) in result.data.iter_mut().enumerate() {
+            for &count in counts {
+                *word |= planes.iter().enumerate().fold(!0, |acc, (bit, plane)| {
+                    if count >> bit & 1 == 1 {
+                        acc & plane[i]
+                    } else {
+                        acc & !plane[i]
+                    }
+                });
+            }
+        }
+        result.clear_padding();
+        result
+    }
+
+    /// Number of neighbours of `pos` in `directions` that are on.
+    pub fn count_neighbours(&self, pos: Pos, directions: &[Direction]) -> usize {
+        directions
+            .iter()
+            .filter_map(|&dir| pos.step(dir))
+            .filter(|&p| self.get(p))
+            .count()
+    }
+
+    fn row_words(&self, row: usize) -> &[u64] {
+        &self.data[row * self.words..(row + 1) * self.words]
+    }
+
+    fn index(&self, pos: Pos) -> (usize, usize) {
+        (pos.row() * self.words + pos.col() / BITS, pos.col() % BITS)
+    }
+
+    fn last_word_mask(&self) -> u64 {
+        match self.cols % BITS {
+            0 => !0,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    fn clear_padding(&mut self) {
+        if self.words == 0 {
+            return;
+        }
+        let mask = self.last_word_mask();
+        for row in 0..self.rows {
+            self.data[(row + 1) * self.words - 1] &= mask;
+        }
+    }
+
+    fn check_bounds(&self, pos: Pos) {
+        if !self.contains(pos) {
+            panic!(
+                "Position {:?} is outside of grid with {} rows and {} cols",
+                pos, self.rows, self.cols
+            );
+        }
+    }
+
+    fn check_same_size(&self, other: &BitGrid) {
+        assert!(
+            self.rows == other.rows && self.cols == other.cols,
+            "Grids of size {}x{} and {}x{} can't be combined",
+            self.rows,
+            self.cols,
+            other.rows,
+            other.cols
+        );
+    }
+
+    fn zip_with<F>(&self, other: &BitGrid, f: F) -> BitGrid
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        self.check_same_size(other);
+        Self {
+            rows: self.rows,
+            cols: self.cols,
+            words: self.words,
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+        }
+    }
+}
+
+/// Shifts the bits of one row towards higher cols if `col_step` is positive, lower otherwise.
+fn shift_words(source: &[u64], dest: &mut [u64], col_step: isize) {
+    let words = source.len() as isize;
+    let word_step = col_step.div_euclid(BITS as isize);
+    let bit_step = col_step.rem_euclid(BITS as isize) as u32;
+    for (i, dest) in dest.iter_mut().enumerate() {
+        // Word i is made up of the high bits of word i - word_step - 1 and the low bits of word
+        // i - word_step.
+        let word = |j: isize| {
+            if (0..words).contains(&j) {
+                source[j as usize]
+            } else {
+                0
+            }
+        };
+        let j = i as isize - word_step;
+        *dest = if bit_step == 0 {
+            word(j)
+        } else {
+            word(j) << bit_step | word(j - 1) >> (BITS as u32 - bit_step)
+        };
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a ^ b)
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> Self::Output {
+        let mut inverted = self.zip_with(self, |a, _| !a);
+        inverted.clear_padding();
+        inverted
+    }
+}
+
+impl Debug for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                write!(f, "{}", if self.get(Pos(row, col)) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::grid::{Direction, Grid, Pos};
+
+    use super::BitGrid;
+
+    fn parse(s: &str) -> BitGrid {
+        BitGrid::from_grid(&s.parse::<Grid<char>>().unwrap().map(|&c| c == '#'))
+    }
+
+    fn render(grid: &BitGrid) -> String {
+        format!("{:?}", grid)
+    }
+
+    #[test]
+    fn get_and_set() {
+        let mut grid = BitGrid::new(3, 130);
+        grid.set(Pos::new(1, 0), true);
+        grid.set(Pos::new(1, 64), true);
+        grid.set(Pos::new(2, 129), true);
+        assert!(grid.get(Pos::new(1, 64)));
+        assert!(!grid.get(Pos::new(1, 63)));
+        assert!(!grid.get(Pos::new(5, 5)));
+        assert_eq!(grid.count_ones(), 3);
+        assert_eq!(
+            grid.ones().collect::<Vec<_>>(),
+            [Pos::new(1, 0), Pos::new(1, 64), Pos::new(2, 129)]
+        );
+        grid.set(Pos::new(1, 64), false);
+        assert_eq!(grid.count_ones(), 2);
+    }
+
+    #[test]
+    fn round_trip() {
+        let grid = "#..\n.##".parse::<Grid<char>>().unwrap().map(|&c| c == '#');
+        let bits = BitGrid::from_grid(&grid);
+        assert_eq!(render(&bits), "#..\n.##\n");
+        assert!(bits.to_grid() == grid);
+    }
+
+    #[test]
+    fn shifts() {
+        let grid = parse("#..\n.#.\n..#");
+        assert_eq!(render(&grid.shifted(Direction::Right)), ".#.\n..#\n...\n");
+        assert_eq!(render(&grid.shifted(Direction::Up)), ".#.\n..#\n...\n");
+        assert_eq!(
+            render(&grid.shifted(Direction::DownLeft)),
+            "...\n...\n#..\n"
+        );
+        assert_eq!(grid.shifted_by(0, 3).count_ones(), 0);
+    }
+
+    #[test]
+    fn shifts_across_words() {
+        let mut grid = BitGrid::new(1, 200);
+        grid.set(Pos::new(0, 63), true);
+        grid.set(Pos::new(0, 199), true);
+        let right = grid.shifted_by(0, 70);
+        assert_eq!(right.ones().collect::<Vec<_>>(), [Pos::new(0, 133)]);
+        let left = grid.shifted_by(0, -63);
+        assert_eq!(
+            left.ones().collect::<Vec<_>>(),
+            [Pos::new(0, 0), Pos::new(0, 136)]
+        );
+    }
+
+    #[test]
+    fn boolean_ops() {
+        let a = parse("##.\n...");
+        let b = parse(".##\n..#");
+        assert_eq!(render(&(&a & &b)), ".#.\n...\n");
+        assert_eq!(render(&(&a | &b)), "###\n..#\n");
+        assert_eq!(render(&(&a ^ &b)), "#.#\n..#\n");
+        assert_eq!(render(&!&a), "..#\n###\n");
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&parse("..#\n###")));
+    }
+
+    #[test]
+    fn row_bits() {
+        let mut grid = BitGrid::new(2, 7);
+        grid.set_row_bits(1, 0b1_1110_0001);
+        assert_eq!(grid.row_bits(1), 0b110_0001);
+        assert_eq!(render(&grid), ".......\n#....##\n");
+        grid.push_rows(2);
+        assert_eq!(grid.rows(), 4);
+        assert_eq!(grid.row_bits(3), 0);
+    }
+
+    #[test]
+    fn neighbour_counts() {
+        let grid = parse(".#...\n..#..\n###..\n.....");
+        for count in 0..=8 {
+            let mask = grid.neighbours_in(&Direction::ALL, &[count]);
+            for row in 0..grid.rows() {
+                for col in 0..grid.cols() {
+                    let pos = Pos::new(row, col);
+                    let expected = grid.count_neighbours(pos, &Direction::ALL) == count as usize;
+                    assert_eq!(mask.get(pos), expected, "{:?} {}", pos, count);
+                }
+            }
+        }
+
+        // One generation of a glider.
+        let born = grid.neighbours_in(&Direction::ALL, &[3]);
+        let survive = &grid & &grid.neighbours_in(&Direction::ALL, &[2]);
+        assert_eq!(render(&(&born | &survive)), ".....\n#.#..\n.##..\n.#...\n");
+    }
+
+    #[test]
+    fn hashing() {
+        let a = parse("#.\n.#");
+        let b = parse("#.\n..");
+        let mut c = b.clone();
+        c.set(Pos::new(1, 1), true);
+        let set = [a.clone(), b, c].into_iter().collect::<HashSet<_>>();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&a));
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{Direction, Pos};
+
+type Vec3 = [i32; 3];
+
+fn neg([x, y, z]: Vec3) -> Vec3 {
+    [-x, -y, -z]
+}
+
+/// Where a face of the net ended up when the net was folded into a cube. `right` and `down`
+/// are the directions of increasing col and row on the face, `normal` points out of the cube.
+#[derive(Copy, Clone, Debug)]
+struct Frame {
+    normal: Vec3,
+    right: Vec3,
+    down: Vec3,
+}
+
+impl Frame {
+    fn vector(&self, dir: Direction) -> Vec3 {
+        match dir {
+            Direction::Up => neg(self.down),
+            Direction::Right => self.right,
+            Direction::Down => self.down,
+            Direction::Left => neg(self.right),
+            _ => panic!("Only orthogonal directions are allowed on a cube"),
+        }
+    }
+
+    fn direction(&self, vector: Vec3) -> Direction {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .find(|&dir| self.vector(dir) == vector)
+            .expect("Vector must lie in the plane of the face")
+    }
+
+    /// The frame of the face on the other side of the edge in direction `dir`, once that face
+    /// has been folded down to form the cube.
+    fn fold(&self, dir: Direction) -> Frame {
+        let (right, down) = match dir {
+            Direction::Up => (self.right, self.normal),
+            Direction::Right => (neg(self.normal), self.down),
+            Direction::Down => (self.right, neg(self.normal)),
+            Direction::Left => (self.normal, self.down),
+            _ => panic!("Only orthogonal directions are allowed on a cube"),
+        };
+        Frame {
+            normal: self.vector(dir),
+            right,
+            down,
+        }
+    }
+}
+
+/// A cube net drawn on a grid, e.g. the map of 2022 day 22. The six faces are found
+/// automatically and walking off the edge of one face continues on the face it's glued to.
+pub struct CubeNet {
+    side: usize,
+    faces: HashMap<Pos, Frame>,
+    by_normal: HashMap<Vec3, Pos>,
+}
+
+impl CubeNet {
+    /// `cells` are all positions that are part of the net.
+    pub fn new<I>(cells: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = Pos>,
+    {
+        let cells = cells.into_iter().collect::<HashSet<_>>();
+        let side = (1..)
+            .take_while(|s| 6 * s * s <= cells.len())
+            .last()
+            .filter(|s| 6 * s * s == cells.len())
+            .ok_or_else(|| format!("{} cells can't be folded into a cube", cells.len()))?;
+
+        let tiles = cells
+            .iter()
+            .map(|p| Pos::new(p.row() / side, p.col() / side))
+            .collect::<HashSet<_>>();
+        if tiles.len() != 6 {
+            return Err(format!(
+                "Expected 6 faces of size {} but found {}",
+                side,
+                tiles.len()
+            ));
+        }
+
+        let first = *tiles.iter().min_by_key(|t| (t.row(), t.col())).unwrap();
+        let mut faces = HashMap::new();
+        faces.insert(
+            first,
+            Frame {
+                normal: [0, 0, 1],
+                right: [1, 0, 0],
+                down: [0, 1, 0],
+            },
+        );
+        let mut queue = VecDeque::from([first]);
+        while let Some(tile) = queue.pop_front() {
+            let frame = faces[&tile];
+            for dir in Direction::ORTHOGONAL {
+                if let Some(next) = tile.step(dir) {
+                    if tiles.contains(&next) && !faces.contains_key(&next) {
+                        faces.insert(next, frame.fold(dir));
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        let by_normal = faces
+            .iter()
+            .map(|(&tile, frame)| (frame.normal, tile))
+            .collect::<HashMap<_, _>>();
+        if faces.len() != 6 || by_normal.len() != 6 {
+            return Err("The faces don't fold into a cube".to_string());
+        }
+
+        Ok(Self {
+            side,
+            faces,
+            by_normal,
+        })
+    }
+
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// The top left corner of each face.
+    pub fn faces(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.faces
+            .keys()
+            .map(|t| Pos::new(t.row() * self.side, t.col() * self.side))
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.faces.contains_key(&self.tile(pos))
+    }
+
+    /// Takes one step from `pos` in direction `dir`. If that walks off the edge of a face the
+    /// step continues on the glued face, and the direction is changed to match.
+    pub fn step(&self, pos: Pos, dir: Direction) -> (Pos, Direction) {
+        let tile = self.tile(pos);
+        let frame = self
+            .faces
+            .get(&tile)
+            .unwrap_or_else(|| panic!("{:?} is not on the cube", pos));
+        let (row, col) = (pos.row() % self.side, pos.col() % self.side);
+
+        let next = match dir {
+            Direction::Up => row.checked_sub(1).map(|r| (r, col)),
+            Direction::Down => Some(row + 1).filter(|&r| r < self.side).map(|r| (r, col)),
+            Direction::Left => col.checked_sub(1).map(|c| (row, c)),
+            Direction::Right => Some(col + 1).filter(|&c| c < self.side).map(|c| (row, c)),
+            _ => panic!("Only orthogonal directions are allowed on a cube"),
+        };
+        if let Some((row, col)) = next {
+            return (self.global(tile, row, col), dir);
+        }
+
+        // Offset along the edge we're crossing, measured along `tangent`.
+        let (tangent, offset) = match dir {
+            Direction::Up | Direction::Down => (frame.right, col),
+            _ => (frame.down, row),
+        };
+        let edge = frame.vector(dir);
+        let next_tile = self.by_normal[&edge];
+        let next_frame = self.faces[&next_tile];
+        let next_dir = next_frame.direction(neg(frame.normal));
+
+        let last = self.side - 1;
+        let along = |axis: Vec3| {
+            if axis == tangent {
+                Some(offset)
+            } else if axis == neg(tangent) {
+                Some(last - offset)
+            } else {
+                None
+            }
+        };
+        let row = match next_dir {
+            Direction::Down => 0,
+            Direction::Up => last,
+            _ => along(next_frame.down).expect("Tangent must lie along a face axis"),
+        };
+        let col = match next_dir {
+            Direction::Right => 0,
+            Direction::Left => last,
+            _ => along(next_frame.right).expect("Tangent must lie along a face axis"),
+        };
+        (self.global(next_tile, row, col), next_dir)
+    }
+
+    fn tile(&self, pos: Pos) -> Pos {
+        Pos::new(pos.row() / self.side, pos.col() / self.side)
+    }
+
+    fn global(&self, tile: Pos, row: usize, col: usize) -> Pos {
+        Pos::new(tile.row() * self.side + row, tile.col() * self.side + col)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::{Direction, Pos};
+
+    use super::CubeNet;
+
+    const EXAMPLE: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.";
+
+    const INPUT_LAYOUT: &str = " ##
+ #
+##
+#";
+
+    fn net(drawing: &str, side: usize) -> CubeNet {
+        let cells = drawing.lines().enumerate().flat_map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, c)| c != ' ')
+                .flat_map(move |(col, _)| {
+                    (0..side).flat_map(move |r| {
+                        (0..side).map(move |c| Pos::new(row * side + r, col * side + c))
+                    })
+                })
+        });
+        CubeNet::new(cells).unwrap()
+    }
+
+    fn example() -> CubeNet {
+        let cells = EXAMPLE.lines().enumerate().flat_map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|&(_, c)| c != ' ')
+                .map(move |(col, _)| Pos::new(row, col))
+        });
+        CubeNet::new(cells).unwrap()
+    }
+
+    #[test]
+    fn faces() {
+        let cube = example();
+        assert_eq!(cube.side(), 4);
+        assert_eq!(cube.faces().count(), 6);
+        assert!(cube.contains(Pos::new(5, 0)));
+        assert!(!cube.contains(Pos::new(0, 0)));
+    }
+
+    #[test]
+    fn invalid_nets() {
+        assert!(CubeNet::new([Pos::new(0, 0)]).is_err());
+        let row = (0..6).map(|c| Pos::new(0, c));
+        assert!(CubeNet::new(row).is_err());
+    }
+
+    #[test]
+    fn example_transitions() {
+        let cube = example();
+        // The two transitions described in the puzzle text.
+        assert_eq!(
+            cube.step(Pos::new(5, 11), Direction::Right),
+            (Pos::new(8, 14), Direction::Down)
+        );
+        assert_eq!(
+            cube.step(Pos::new(11, 10), Direction::Down),
+            (Pos::new(7, 1), Direction::Up)
+        );
+        assert_eq!(
+            cube.step(Pos::new(0, 8), Direction::Left),
+            (Pos::new(4, 4), Direction::Down)
+        );
+        assert_eq!(
+            cube.step(Pos::new(1, 1 + 8), Direction::Right),
+            (Pos::new(1, 10), Direction::Right)
+        );
+    }
+
+    #[test]
+    fn example_walk() {
+        let cube = example();
+        let map = EXAMPLE
+            .lines()
+            .map(|l| l.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut pos = Pos::new(0, 8);
+        let mut dir = Direction::Right;
+        for (steps, turn) in [
+            (10, 'R'),
+            (5, 'L'),
+            (5, 'R'),
+            (10, 'L'),
+            (4, 'R'),
+            (5, 'L'),
+            (5, ' '),
+        ] {
+            for _ in 0..steps {
+                let (next, next_dir) = cube.step(pos, dir);
+                if map[next.row()][next.col()] == '#' {
+                    break;
+                }
+                (pos, dir) = (next, next_dir);
+            }
+            dir = match turn {
+                'R' => dir.turn_cw(),
+                'L' => dir.turn_ccw(),
+                _ => dir,
+            };
+        }
+        assert_eq!((pos, dir), (Pos::new(4, 6), Direction::Up));
+    }
+
+    #[test]
+    fn every_edge_is_glued_both_ways() {
+        for (drawing, side) in [
+            (INPUT_LAYOUT, 3),
+            (" #\n####\n #", 2),
+            ("##\n ##\n  ##", 2),
+            ("  #\n###\n  ##", 1),
+        ] {
+            let cube = net(drawing, side);
+            for face in cube.faces().collect::<Vec<_>>() {
+                for i in 0..side {
+                    let last = side - 1;
+                    for (pos, dir) in [
+                        (Pos::new(face.row(), face.col() + i), Direction::Up),
+                        (Pos::new(face.row() + last, face.col() + i), Direction::Down),
+                        (Pos::new(face.row() + i, face.col()), Direction::Left),
+                        (
+                            Pos::new(face.row() + i, face.col() + last),
+                            Direction::Right,
+                        ),
+                    ] {
+                        let (next, next_dir) = cube.step(pos, dir);
+                        assert!(cube.contains(next));
+                        let (returned, returned_dir) = cube.step(next, next_dir.opposite());
+                        assert_eq!(returned, pos, "{:?} {:?} via {:?}", pos, dir, next);
+                        assert_eq!(returned_dir.opposite(), dir);
+                    }
+                }
+            }
+        }
+    }
+}
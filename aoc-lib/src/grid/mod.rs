@@ -1,12 +1,24 @@
 use std::{
+    collections::HashMap,
     fmt::{Debug, Display},
+    hash::{Hash, Hasher},
     iter::Map,
+    ops::{Index, IndexMut},
     str::FromStr,
 };
 
 use itertools::Itertools;
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+pub mod bits;
+pub mod cube;
+pub mod search;
+pub mod sight;
+pub mod sparse;
+pub mod view;
+
+use view::GridView;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum Direction {
     Up,
     UpRight,
@@ -18,8 +30,116 @@ pub enum Direction {
     UpLeft,
 }
 
+impl Direction {
+    /// Up, right, down and left, going clockwise.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All 8 directions going clockwise, starting with up.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// The change in row and col when taking one step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    /// Turns 90 degrees clockwise.
+    pub fn turn_cw(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// Turns 90 degrees counter-clockwise.
+    pub fn turn_ccw(self) -> Direction {
+        self.rotate(6)
+    }
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_cw_45(self) -> Direction {
+        self.rotate(1)
+    }
+
+    /// Turns 45 degrees counter-clockwise.
+    pub fn turn_ccw_45(self) -> Direction {
+        self.rotate(7)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_orthogonal(self) -> bool {
+        Direction::ORTHOGONAL.contains(&self)
+    }
+
+    /// Rotates clockwise in steps of 45 degrees.
+    fn rotate(self, steps: usize) -> Direction {
+        let index = Direction::ALL.iter().position(|&d| d == self).unwrap();
+        Direction::ALL[(index + steps) % 8]
+    }
+}
+
+/// Accepts arrows (`^>v<`), letters (`UDLR`) and compass points (`N`, `NE`, `E`, ...).
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "^" | "U" | "N" => Ok(Direction::Up),
+            "NE" => Ok(Direction::UpRight),
+            ">" | "R" | "E" => Ok(Direction::Right),
+            "SE" => Ok(Direction::DownRight),
+            "v" | "D" | "S" => Ok(Direction::Down),
+            "SW" => Ok(Direction::DownLeft),
+            "<" | "L" | "W" => Ok(Direction::Left),
+            "NW" => Ok(Direction::UpLeft),
+            _ => Err(format!("Unknown direction {}", s)),
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        c.encode_utf8(&mut [0; 4]).parse()
+    }
+}
+
+/// How positions outside of the grid are treated when moving around in it.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Topology {
+    /// Moving off an edge leaves the grid.
+    #[default]
+    Bounded,
+    /// Moving off an edge re-enters the grid on the opposite edge.
+    Wrapping,
+}
+
 pub struct Grid<T> {
     data: Vec<Vec<T>>,
+    topology: Topology,
 }
 
 impl<T> Debug for Grid<T>
@@ -44,13 +164,84 @@ where
     fn clone(&self) -> Self {
         Self {
             data: self.data.clone(),
+            topology: self.topology,
         }
     }
 }
 
+impl<T> PartialEq for Grid<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data && self.topology == other.topology
+    }
+}
+
+impl<T> Eq for Grid<T> where T: Eq {}
+
+impl<T> Hash for Grid<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+        self.topology.hash(state);
+    }
+}
+
 impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![vec![fill; cols]; rows],
+            topology: Topology::Bounded,
+        }
+    }
+
+    /// Panics if the rows are not all of the same length.
+    pub fn from_rows(data: Vec<Vec<T>>) -> Self {
+        if let Some(first) = data.first() {
+            let cols = first.len();
+            if let Some((row, _)) = data.iter().find_position(|r| r.len() != cols) {
+                panic!("Row {} has a different length than row 0", row);
+            }
+        }
+        Self {
+            data,
+            topology: Topology::Bounded,
+        }
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    pub fn rows(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.data.first().map(|row| row.len()).unwrap_or(0)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row() < self.rows() && pos.col() < self.cols()
+    }
+
     pub fn insert(&mut self, pos: Pos, value: T) {
-        self.data[pos.0][pos.1] = value;
+        self[pos] = value;
     }
 
     pub fn get(&self, pos: Pos) -> Option<&T> {
@@ -61,6 +252,104 @@ impl<T> Grid<T> {
         }
     }
 
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if let Some(row) = self.data.get_mut(pos.row()) {
+            row.get_mut(pos.col())
+        } else {
+            None
+        }
+    }
+
+    pub fn swap(&mut self, a: Pos, b: Pos) {
+        self.check_bounds(a);
+        self.check_bounds(b);
+        if a.row() == b.row() {
+            self.data[a.row()].swap(a.col(), b.col());
+        } else {
+            let (first, second) = if a.row() < b.row() { (a, b) } else { (b, a) };
+            let (top, bottom) = self.data.split_at_mut(second.row());
+            std::mem::swap(
+                &mut top[first.row()][first.col()],
+                &mut bottom[0][second.col()],
+            );
+        }
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            data: self
+                .data
+                .iter()
+                .map(|row| row.iter().map(&mut f).collect())
+                .collect(),
+            topology: self.topology,
+        }
+    }
+
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView::new(self)
+    }
+
+    pub fn transpose(&self) -> GridView<'_, T> {
+        self.view().transpose()
+    }
+
+    pub fn rotate_cw(&self) -> GridView<'_, T> {
+        self.view().rotate_cw()
+    }
+
+    pub fn rotate_ccw(&self) -> GridView<'_, T> {
+        self.view().rotate_ccw()
+    }
+
+    pub fn flip_horizontal(&self) -> GridView<'_, T> {
+        self.view().flip_horizontal()
+    }
+
+    pub fn flip_vertical(&self) -> GridView<'_, T> {
+        self.view().flip_vertical()
+    }
+
+    pub fn orientations(&self) -> impl Iterator<Item = GridView<'_, T>> {
+        self.view().orientations()
+    }
+
+    fn path_iter(&self, pos: Pos, row_step: isize, col_step: isize) -> PathIterator<'_, T> {
+        PathIterator {
+            data: &self.data,
+            pos,
+            row_step,
+            col_step,
+            start: pos,
+            wrapping: self.topology == Topology::Wrapping,
+            done: false,
+        }
+    }
+
+    fn neighbour(&self, pos: Pos, dir: Direction) -> Option<Pos> {
+        match self.topology {
+            Topology::Bounded => pos.step(dir).filter(|&p| self.contains(p)),
+            Topology::Wrapping if self.contains(pos) => {
+                Some(pos.offset_wrapping(dir.delta(), self.rows(), self.cols()))
+            }
+            Topology::Wrapping => None,
+        }
+    }
+
+    fn check_bounds(&self, pos: Pos) {
+        if !self.contains(pos) {
+            panic!(
+                "Position {:?} is outside of grid with {} rows and {} cols",
+                pos,
+                self.rows(),
+                self.cols()
+            );
+        }
+    }
+
     pub fn iter(&self) -> RowWiseIter<'_, T> {
         RowWiseIter {
             data: &self.data,
@@ -70,71 +359,31 @@ impl<T> Grid<T> {
     }
 
     pub fn up(&self, pos: Pos) -> PathIterator<'_, T> {
-        PathIterator {
-            data: &self.data,
-            pos,
-            row_step: -1,
-            col_step: 0,
-        }
+        self.path_iter(pos, -1, 0)
     }
     pub fn down(&self, pos: Pos) -> PathIterator<'_, T> {
-        PathIterator {
-            data: &self.data,
-            pos,
-            row_step: 1,
-            col_step: 0,
-        }
+        self.path_iter(pos, 1, 0)
     }
     pub fn left(&self, pos: Pos) -> PathIterator<'_, T> {
-        PathIterator {
-            data: &self.data,
-            pos,
-            row_step: 0,
-            col_step: -1,
-        }
+        self.path_iter(pos, 0, -1)
     }
     pub fn right(&self, pos: Pos) -> PathIterator<'_, T> {
-        PathIterator {
-            data: &self.data,
-            pos,
-            row_step: 0,
-            col_step: 1,
-        }
+        self.path_iter(pos, 0, 1)
     }
 
     pub fn up_right(&self, pos: Pos) -> PathIterator<'_, T> {
-        PathIterator {
-            data: &self.data,
-            pos,
-            row_step: -1,
-            col_step: 1,
-        }
+        self.path_iter(pos, -1, 1)
     }
 
     pub fn down_right(&self, pos: Pos) -> PathIterator<'_, T> {
-        PathIterator {
-            data: &self.data,
-            pos,
-            row_step: 1,
-            col_step: 1,
-        }
+        self.path_iter(pos, 1, 1)
     }
 
     pub fn up_left(&self, pos: Pos) -> PathIterator<'_, T> {
-        PathIterator {
-            data: &self.data,
-            pos,
-            row_step: -1,
-            col_step: -1,
-        }
+        self.path_iter(pos, -1, -1)
     }
     pub fn down_left(&self, pos: Pos) -> PathIterator<'_, T> {
-        PathIterator {
-            data: &self.data,
-            pos,
-            row_step: 1,
-            col_step: -1,
-        }
+        self.path_iter(pos, 1, -1)
     }
 
     pub fn path(&self, dir: Direction, pos: Pos) -> PathIterator<'_, T> {
@@ -149,14 +398,45 @@ impl<T> Grid<T> {
             Direction::UpLeft => self.up_left(pos),
         }
     }
-    pub fn step(&self, pos: Pos, step_row: isize, step_col: isize) -> PathIterator<'_, T> {
-        PathIterator {
-            data: &self.data,
+    pub fn neighbours4(&self, pos: Pos) -> Neighbours<'_, T> {
+        self.neighbours_with(pos, &Direction::ORTHOGONAL)
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> Neighbours<'_, T> {
+        self.neighbours_with(pos, &Direction::ALL)
+    }
+
+    pub fn neighbours_with<'a>(
+        &'a self,
+        pos: Pos,
+        directions: &'a [Direction],
+    ) -> Neighbours<'a, T> {
+        Neighbours {
+            grid: self,
             pos,
-            row_step: step_row,
-            col_step: step_col,
+            directions: directions.iter(),
         }
     }
+
+    pub fn step(&self, pos: Pos, step_row: isize, step_col: isize) -> PathIterator<'_, T> {
+        self.path_iter(pos, step_row, step_col)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.check_bounds(pos);
+        &self.data[pos.row()][pos.col()]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.check_bounds(pos);
+        &mut self.data[pos.row()][pos.col()]
+    }
 }
 
 #[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
@@ -205,6 +485,40 @@ impl Pos {
     pub fn distance_to(&self, other: &Pos) -> usize {
         self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
     }
+
+    /// One step in direction `dir`, or `None` if that would make the row or col negative. Unlike
+    /// [`Pos::up`] and [`Pos::left`] this never wraps around.
+    pub fn step(self, dir: Direction) -> Option<Pos> {
+        self.offset(dir.delta())
+    }
+
+    /// Takes `n` steps in direction `dir` on a grid with `rows` rows and `cols` cols, re-entering
+    /// on the opposite side whenever an edge is crossed.
+    pub fn step_wrapping(self, dir: Direction, n: usize, rows: usize, cols: usize) -> Pos {
+        let (row_step, col_step) = dir.delta();
+        // Whole laps don't move anything, and leaving them out keeps big `n` from overflowing.
+        let (row_n, col_n) = ((n % rows) as isize, (n % cols) as isize);
+        self.offset_wrapping((row_step * row_n, col_step * col_n), rows, cols)
+    }
+
+    pub fn offset_wrapping(
+        self,
+        (row_step, col_step): (isize, isize),
+        rows: usize,
+        cols: usize,
+    ) -> Pos {
+        Pos(
+            (self.0 as isize + row_step).rem_euclid(rows as isize) as usize,
+            (self.1 as isize + col_step).rem_euclid(cols as isize) as usize,
+        )
+    }
+
+    fn offset(self, (row_step, col_step): (isize, isize)) -> Option<Pos> {
+        Some(Pos(
+            self.0.checked_add_signed(row_step)?,
+            self.1.checked_add_signed(col_step)?,
+        ))
+    }
 }
 
 pub struct RowWiseIter<'a, T> {
@@ -239,24 +553,184 @@ where
     }
 }
 
-pub struct Point<T> {
-    pub pos: Pos,
+pub struct Point<T, P = Pos> {
+    pub pos: P,
     pub value: T,
 }
 
-trait ParseChar {
-    fn parse(c: char) -> Self;
+/// Converts a single character of puzzle input to a grid cell, or `None` if the character
+/// isn't valid. Use [`char_enum!`](crate::char_enum) to implement it for an enum of tiles.
+pub trait ParseChar: Sized {
+    fn parse(c: char) -> Option<Self>;
 }
 
 impl ParseChar for char {
-    fn parse(c: char) -> Self {
-        c
+    fn parse(c: char) -> Option<Self> {
+        Some(c)
     }
 }
 
 impl ParseChar for u32 {
-    fn parse(c: char) -> Self {
-        c as u32 - '0' as u32
+    fn parse(c: char) -> Option<Self> {
+        c.to_digit(10)
+    }
+}
+
+/// Defines an enum where every variant is written as a single character, and implements
+/// [`ParseChar`] and `Display` for it, e.g.
+/// `char_enum! { enum Tile { Wall = '#', Floor = '.' } }`.
+#[macro_export]
+macro_rules! char_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $c:literal),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($(#[$variant_meta])* $variant),+
+        }
+
+        impl $crate::grid::ParseChar for $name {
+            fn parse(c: char) -> Option<Self> {
+                match c {
+                    $($c => Some($name::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let c = match self {
+                    $($name::$variant => $c),+
+                };
+                write!(f, "{}", c)
+            }
+        }
+    };
+}
+
+/// Why a grid couldn't be parsed. Lines and columns start at 1.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ParseGridError {
+    UnknownChar {
+        line: usize,
+        column: usize,
+        c: char,
+    },
+    RaggedLine {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    DuplicateMarker {
+        line: usize,
+        column: usize,
+        c: char,
+    },
+    MissingMarker(char),
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::UnknownChar { line, column, c } => {
+                write!(
+                    f,
+                    "Unknown character {:?} at line {}, column {}",
+                    c, line, column
+                )
+            }
+            ParseGridError::RaggedLine {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Line {} has {} columns but the first line has {}",
+                line, found, expected
+            ),
+            ParseGridError::DuplicateMarker { line, column, c } => write!(
+                f,
+                "Marker {:?} at line {}, column {} was already found earlier",
+                c, line, column
+            ),
+            ParseGridError::MissingMarker(c) => write!(f, "Marker {:?} not found", c),
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl<T> Grid<T>
+where
+    T: ParseChar,
+{
+    /// Parses a grid where each character in `markers` appears exactly once, e.g. the start
+    /// and end of a maze. Returns the grid and the position of every marker. If `floor` is given
+    /// the markers are replaced by it, otherwise they are parsed like any other character.
+    pub fn parse_with_markers(
+        input: &str,
+        markers: &[char],
+        floor: Option<T>,
+    ) -> Result<(Self, HashMap<char, Pos>), ParseGridError>
+    where
+        T: Clone,
+    {
+        let mut found = HashMap::new();
+        let grid = Self::parse_cells(input, |pos, c| {
+            if markers.contains(&c) {
+                if found.insert(c, pos).is_some() {
+                    return Err(ParseGridError::DuplicateMarker {
+                        line: pos.row() + 1,
+                        column: pos.col() + 1,
+                        c,
+                    });
+                }
+                if let Some(floor) = &floor {
+                    return Ok(floor.clone());
+                }
+            }
+            T::parse(c).ok_or(ParseGridError::UnknownChar {
+                line: pos.row() + 1,
+                column: pos.col() + 1,
+                c,
+            })
+        })?;
+        if let Some(&missing) = markers.iter().find(|c| !found.contains_key(c)) {
+            return Err(ParseGridError::MissingMarker(missing));
+        }
+        Ok((grid, found))
+    }
+
+    fn parse_cells<F>(input: &str, mut parse: F) -> Result<Self, ParseGridError>
+    where
+        F: FnMut(Pos, char) -> Result<T, ParseGridError>,
+    {
+        let mut data: Vec<Vec<T>> = Vec::new();
+        for (row, line) in input.lines().enumerate() {
+            let cells = line
+                .chars()
+                .enumerate()
+                .map(|(col, c)| parse(Pos(row, col), c))
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(first) = data.first() {
+                if first.len() != cells.len() {
+                    return Err(ParseGridError::RaggedLine {
+                        line: row + 1,
+                        expected: first.len(),
+                        found: cells.len(),
+                    });
+                }
+            }
+            data.push(cells);
+        }
+        Ok(Self {
+            data,
+            topology: Topology::Bounded,
+        })
     }
 }
 
@@ -264,22 +738,29 @@ impl<T> FromStr for Grid<T>
 where
     T: ParseChar,
 {
-    type Err = ();
+    type Err = ParseGridError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let puzzle = input
-            .lines()
-            .map(|line| line.chars().map(|c| ParseChar::parse(c)).collect_vec())
-            .collect_vec();
-        Ok(Self { data: puzzle })
+        Self::parse_cells(input, |pos, c| {
+            T::parse(c).ok_or(ParseGridError::UnknownChar {
+                line: pos.row() + 1,
+                column: pos.col() + 1,
+                c,
+            })
+        })
     }
 }
 
+/// Walks from a position in a fixed direction, starting with the position itself. On a
+/// [`Topology::Wrapping`] grid the path stops right before it would get back to where it started.
 pub struct PathIterator<'a, T> {
     data: &'a Vec<Vec<T>>,
     pos: Pos,
     row_step: isize,
     col_step: isize,
+    start: Pos,
+    wrapping: bool,
+    done: bool,
 }
 
 impl<'a, T> Iterator for PathIterator<'a, T>
@@ -289,16 +770,28 @@ where
     type Item = Point<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
         if let Some(row) = self.data.get(self.pos.0) {
             if let Some(next) = row.get(self.pos.1) {
-                let next_pos = Pos(
-                    self.pos.0.wrapping_add(self.row_step as usize),
-                    self.pos.1.wrapping_add(self.col_step as usize),
-                );
+                let next_pos = if self.wrapping {
+                    self.pos.offset_wrapping(
+                        (self.row_step, self.col_step),
+                        self.data.len(),
+                        row.len(),
+                    )
+                } else {
+                    Pos(
+                        self.pos.0.wrapping_add(self.row_step as usize),
+                        self.pos.1.wrapping_add(self.col_step as usize),
+                    )
+                };
                 let point = Point {
                     pos: self.pos,
                     value: next.clone(),
                 };
+                self.done = self.wrapping && next_pos == self.start;
                 self.pos = next_pos;
                 Some(point)
             } else {
@@ -310,6 +803,28 @@ where
     }
 }
 
+pub struct Neighbours<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Pos,
+    directions: std::slice::Iter<'a, Direction>,
+}
+
+impl<'a, T> Iterator for Neighbours<'a, T> {
+    type Item = Point<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for &dir in self.directions.by_ref() {
+            if let Some(pos) = self.grid.neighbour(self.pos, dir) {
+                return Some(Point {
+                    pos,
+                    value: &self.grid[pos],
+                });
+            }
+        }
+        None
+    }
+}
+
 impl<'a, T> PathIterator<'a, T> {
     pub fn values(self) -> Map<PathIterator<'a, T>, impl FnMut(Point<T>) -> T>
     where
@@ -318,3 +833,291 @@ impl<'a, T> PathIterator<'a, T> {
         self.map(|p| p.value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Direction, Grid, Neighbours, ParseGridError, Pos, Topology};
+
+    crate::char_enum! {
+        #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+        enum Tile {
+            Wall = '#',
+            Floor = '.',
+        }
+    }
+
+    const INPUT: &str = "abc
+def";
+
+    #[test]
+    fn dimensions() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+        assert_eq!(grid.rows(), 2);
+        assert_eq!(grid.cols(), 3);
+
+        let grid = Grid::new(4, 5, 0);
+        assert_eq!(grid.rows(), 4);
+        assert_eq!(grid.cols(), 5);
+        assert!(grid.iter().all(|p| p.value == 0));
+
+        let grid = Grid::<u32>::from_rows(Vec::new());
+        assert_eq!(grid.rows(), 0);
+        assert_eq!(grid.cols(), 0);
+    }
+
+    #[test]
+    fn contains() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+        assert!(grid.contains(Pos::new(0, 0)));
+        assert!(grid.contains(Pos::new(1, 2)));
+        assert!(!grid.contains(Pos::new(2, 0)));
+        assert!(!grid.contains(Pos::new(0, 3)));
+        assert!(!grid.contains(Pos::new(0, 0).up()));
+    }
+
+    #[test]
+    fn index() {
+        let mut grid = INPUT.parse::<Grid<char>>().unwrap();
+        assert_eq!(grid[Pos::new(1, 1)], 'e');
+        grid[Pos::new(1, 1)] = 'x';
+        assert_eq!(grid[Pos::new(1, 1)], 'x');
+
+        *grid.get_mut(Pos::new(0, 2)).unwrap() = 'y';
+        assert_eq!(grid.get(Pos::new(0, 2)), Some(&'y'));
+        assert!(grid.get_mut(Pos::new(0, 3)).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+        let _ = grid[Pos::new(0, 3)];
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_rows_ragged() {
+        Grid::from_rows(vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn swap() {
+        let mut grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]);
+        grid.swap(Pos::new(0, 0), Pos::new(0, 1));
+        assert_eq!(
+            grid.iter().map(|p| p.value).collect::<Vec<_>>(),
+            [2, 1, 3, 4]
+        );
+        grid.swap(Pos::new(1, 1), Pos::new(0, 0));
+        assert_eq!(
+            grid.iter().map(|p| p.value).collect::<Vec<_>>(),
+            [4, 1, 3, 2]
+        );
+        grid.swap(Pos::new(1, 0), Pos::new(1, 0));
+        assert_eq!(
+            grid.iter().map(|p| p.value).collect::<Vec<_>>(),
+            [4, 1, 3, 2]
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = "123\n456\n789".parse::<Grid<u32>>().unwrap();
+        let values = |points: Neighbours<'_, u32>| points.map(|p| *p.value).collect::<Vec<_>>();
+
+        assert_eq!(values(grid.neighbours4(Pos::new(1, 1))), [2, 6, 8, 4]);
+        assert_eq!(values(grid.neighbours4(Pos::new(0, 0))), [2, 4]);
+        assert_eq!(values(grid.neighbours4(Pos::new(2, 2))), [6, 8]);
+        assert_eq!(
+            values(grid.neighbours8(Pos::new(1, 1))),
+            [2, 3, 6, 9, 8, 7, 4, 1]
+        );
+        assert_eq!(values(grid.neighbours8(Pos::new(0, 2))), [6, 5, 2]);
+        assert_eq!(
+            values(grid.neighbours_with(Pos::new(2, 0), &[Direction::Up, Direction::Left])),
+            [4]
+        );
+        assert_eq!(
+            grid.neighbours4(Pos::new(0, 1))
+                .map(|p| p.pos)
+                .collect::<Vec<_>>(),
+            [Pos::new(0, 2), Pos::new(1, 1), Pos::new(0, 0)]
+        );
+    }
+
+    #[test]
+    fn neighbours_outside_grid() {
+        let grid = "12\n34".parse::<Grid<u32>>().unwrap();
+        assert_eq!(grid.neighbours8(Pos::new(5, 5)).count(), 0);
+        assert_eq!(
+            grid.neighbours8(Pos::new(2, 2))
+                .map(|p| *p.value)
+                .collect::<Vec<_>>(),
+            [4]
+        );
+        assert_eq!(grid.neighbours8(Pos::new(usize::MAX, 0)).count(), 0);
+    }
+
+    #[test]
+    fn step_wrapping() {
+        let pos = Pos::new(0, 1);
+        assert_eq!(pos.step_wrapping(Direction::Up, 1, 3, 4), Pos::new(2, 1));
+        assert_eq!(pos.step_wrapping(Direction::Left, 6, 3, 4), Pos::new(0, 3));
+        assert_eq!(
+            pos.step_wrapping(Direction::DownRight, 7, 3, 4),
+            Pos::new(1, 0)
+        );
+        assert_eq!(
+            pos.step_wrapping(Direction::DownRight, usize::MAX, 3, 4),
+            Pos::new(0, 0)
+        );
+        assert_eq!(pos.offset_wrapping((-4, 9), 3, 4), Pos::new(2, 2));
+    }
+
+    #[test]
+    fn wrapping_neighbours() {
+        let grid = "123\n456\n789"
+            .parse::<Grid<u32>>()
+            .unwrap()
+            .with_topology(Topology::Wrapping);
+        let values = |points: Neighbours<'_, u32>| points.map(|p| *p.value).collect::<Vec<_>>();
+
+        assert_eq!(values(grid.neighbours4(Pos::new(0, 0))), [7, 2, 4, 3]);
+        assert_eq!(
+            values(grid.neighbours8(Pos::new(2, 2))),
+            [6, 4, 7, 1, 3, 2, 8, 5]
+        );
+        assert_eq!(grid.neighbours4(Pos::new(3, 0)).count(), 0);
+    }
+
+    #[test]
+    fn wrapping_path() {
+        let grid = "1234\n5678".parse::<Grid<u32>>().unwrap();
+        assert_eq!(
+            grid.right(Pos::new(0, 2)).values().collect::<Vec<_>>(),
+            [3, 4]
+        );
+
+        let grid = grid.with_topology(Topology::Wrapping);
+        assert_eq!(
+            grid.right(Pos::new(0, 2)).values().collect::<Vec<_>>(),
+            [3, 4, 1, 2]
+        );
+        assert_eq!(
+            grid.up_left(Pos::new(0, 0)).values().collect::<Vec<_>>(),
+            [1, 8, 3, 6]
+        );
+        assert_eq!(grid.step(Pos::new(1, 1), 0, 2).count(), 2);
+        assert_eq!(grid.step(Pos::new(1, 1), 0, 0).count(), 1);
+    }
+
+    #[test]
+    fn map() {
+        let grid = "123\n456".parse::<Grid<u32>>().unwrap();
+        let doubled = grid.map(|v| v * 2);
+        assert_eq!(doubled.rows(), 2);
+        assert_eq!(doubled[Pos::new(1, 2)], 12);
+    }
+
+    #[test]
+    fn parse_tiles() {
+        let grid = "#.\n.#".parse::<Grid<Tile>>().unwrap();
+        assert_eq!(grid[Pos::new(0, 0)], Tile::Wall);
+        assert_eq!(grid[Pos::new(0, 1)], Tile::Floor);
+        assert_eq!(format!("{:?}", grid), "#.\n.#\n");
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "#.\n.x".parse::<Grid<Tile>>().unwrap_err(),
+            ParseGridError::UnknownChar {
+                line: 2,
+                column: 2,
+                c: 'x'
+            }
+        );
+        assert_eq!(
+            "12\n3a".parse::<Grid<u32>>().unwrap_err().to_string(),
+            "Unknown character 'a' at line 2, column 2"
+        );
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>().unwrap_err(),
+            ParseGridError::RaggedLine {
+                line: 2,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert!("".parse::<Grid<char>>().unwrap().rows() == 0);
+    }
+
+    #[test]
+    fn parse_with_markers() {
+        let (grid, markers) =
+            Grid::<Tile>::parse_with_markers("#S.\n.E#", &['S', 'E'], Some(Tile::Floor)).unwrap();
+        assert_eq!(markers[&'S'], Pos::new(0, 1));
+        assert_eq!(markers[&'E'], Pos::new(1, 1));
+        assert_eq!(format!("{:?}", grid), "#..\n..#\n");
+
+        let (grid, markers) = Grid::<char>::parse_with_markers("ab\ncd", &['c'], None).unwrap();
+        assert_eq!(grid[markers[&'c']], 'c');
+
+        assert_eq!(
+            Grid::<Tile>::parse_with_markers("S.\n.S", &['S'], Some(Tile::Floor)).unwrap_err(),
+            ParseGridError::DuplicateMarker {
+                line: 2,
+                column: 2,
+                c: 'S'
+            }
+        );
+        assert_eq!(
+            Grid::<Tile>::parse_with_markers("S.\n..", &['S', 'E'], Some(Tile::Floor)).unwrap_err(),
+            ParseGridError::MissingMarker('E')
+        );
+        assert!(Grid::<Tile>::parse_with_markers("S.", &['S'], None).is_err());
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::Up.turn_cw(), Direction::Right);
+        assert_eq!(Direction::Left.turn_cw(), Direction::Up);
+        assert_eq!(Direction::Up.turn_ccw(), Direction::Left);
+        assert_eq!(Direction::UpLeft.turn_cw_45(), Direction::Up);
+        assert_eq!(Direction::Up.turn_ccw_45(), Direction::UpLeft);
+        assert_eq!(Direction::DownRight.opposite(), Direction::UpLeft);
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_cw().turn_ccw(), dir);
+            assert_eq!(dir.turn_cw().turn_cw(), dir.opposite());
+            let (row, col) = dir.delta();
+            assert_eq!(dir.opposite().delta(), (-row, -col));
+        }
+        assert!(Direction::ORTHOGONAL.iter().all(|d| d.is_orthogonal()));
+        assert!(!Direction::UpRight.is_orthogonal());
+    }
+
+    #[test]
+    fn parse_direction() {
+        for (dir, names) in [
+            (Direction::Up, ["^", "U", "N"]),
+            (Direction::Right, [">", "R", "E"]),
+            (Direction::Down, ["v", "D", "S"]),
+            (Direction::Left, ["<", "L", "W"]),
+        ] {
+            for name in names {
+                assert_eq!(name.parse::<Direction>(), Ok(dir));
+            }
+        }
+        assert_eq!("SW".parse::<Direction>(), Ok(Direction::DownLeft));
+        assert_eq!(Direction::try_from('v'), Ok(Direction::Down));
+        assert!(Direction::try_from('x').is_err());
+    }
+
+    #[test]
+    fn step() {
+        assert_eq!(Pos::new(1, 1).step(Direction::UpLeft), Some(Pos::new(0, 0)));
+        assert_eq!(Pos::new(0, 1).step(Direction::Up), None);
+        assert_eq!(Pos::new(1, 0).step(Direction::DownLeft), None);
+        assert_eq!(Pos::new(1, 0).step(Direction::Right), Some(Pos::new(1, 1)));
+    }
+}
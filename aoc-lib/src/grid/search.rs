@@ -0,0 +1,244 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::{Direction, Grid, Point, Pos};
+
+/// Breadth first search from all `sources` at once. `passable` is called with the cell being
+/// left and the cell being entered. Cells that can't be reached are `None` in the returned grid.
+pub fn bfs<T, I, F>(grid: &Grid<T>, sources: I, mut passable: F) -> Grid<Option<usize>>
+where
+    I: IntoIterator<Item = Pos>,
+    F: FnMut(Point<&T>, Point<&T>) -> bool,
+{
+    let mut distances = Grid::new(grid.rows(), grid.cols(), None);
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if grid.contains(source) && distances[source].is_none() {
+            distances[source] = Some(0);
+            queue.push_back((source, 0));
+        }
+    }
+
+    while let Some((pos, distance)) = queue.pop_front() {
+        for next in grid.neighbours4(pos) {
+            if distances[next.pos].is_some() {
+                continue;
+            }
+            let current = Point {
+                pos,
+                value: &grid[pos],
+            };
+            let next_pos = next.pos;
+            if passable(current, next) {
+                distances[next_pos] = Some(distance + 1);
+                queue.push_back((next_pos, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// All cells reachable from `start`, including `start` itself.
+pub fn flood_fill<T, F>(grid: &Grid<T>, start: Pos, passable: F) -> HashSet<Pos>
+where
+    F: FnMut(Point<&T>, Point<&T>) -> bool,
+{
+    bfs(grid, [start], passable)
+        .iter()
+        .filter(|p| p.value.is_some())
+        .map(|p| p.pos)
+        .collect()
+}
+
+/// Splits the grid into regions of cells with equal values.
+pub fn regions<T>(grid: &Grid<T>) -> Vec<Region>
+where
+    T: Eq,
+{
+    regions_by(grid, |a, b| a.value == b.value)
+}
+
+/// Splits the grid into regions where `connected` decides if two neighbouring cells belong
+/// together. Every cell ends up in exactly one region.
+pub fn regions_by<T, F>(grid: &Grid<T>, mut connected: F) -> Vec<Region>
+where
+    F: FnMut(Point<&T>, Point<&T>) -> bool,
+{
+    let mut labelled = Grid::new(grid.rows(), grid.cols(), false);
+    let mut regions = Vec::new();
+
+    for row in 0..grid.rows() {
+        for col in 0..grid.cols() {
+            let pos = Pos::new(row, col);
+            if labelled[pos] {
+                continue;
+            }
+            labelled[pos] = true;
+            let mut cells = HashSet::new();
+            let mut queue = VecDeque::from([pos]);
+            while let Some(current) = queue.pop_front() {
+                cells.insert(current);
+                for next in grid.neighbours4(current) {
+                    if labelled[next.pos] {
+                        continue;
+                    }
+                    let next_pos = next.pos;
+                    let current = Point {
+                        pos: current,
+                        value: &grid[current],
+                    };
+                    if connected(current, next) {
+                        labelled[next_pos] = true;
+                        queue.push_back(next_pos);
+                    }
+                }
+            }
+            regions.push(Region::new(cells));
+        }
+    }
+    regions
+}
+
+pub struct Region {
+    cells: HashSet<Pos>,
+    perimeter: usize,
+    sides: usize,
+}
+
+impl Region {
+    pub fn new(cells: HashSet<Pos>) -> Self {
+        let inside =
+            |pos: Pos, dir: Direction| pos.step(dir).map(|p| cells.contains(&p)).unwrap_or(false);
+
+        let mut perimeter = 0;
+        let mut sides = 0;
+        for &pos in &cells {
+            perimeter += Direction::ORTHOGONAL
+                .iter()
+                .filter(|&&dir| !inside(pos, dir))
+                .count();
+
+            // Every corner of the region starts a new side, so counting corners counts sides.
+            for (a, b, diagonal) in [
+                (Direction::Up, Direction::Right, Direction::UpRight),
+                (Direction::Right, Direction::Down, Direction::DownRight),
+                (Direction::Down, Direction::Left, Direction::DownLeft),
+                (Direction::Left, Direction::Up, Direction::UpLeft),
+            ] {
+                let (a, b, diagonal) = (inside(pos, a), inside(pos, b), inside(pos, diagonal));
+                if (!a && !b) || (a && b && !diagonal) {
+                    sides += 1;
+                }
+            }
+        }
+
+        Self {
+            cells,
+            perimeter,
+            sides,
+        }
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &Pos> {
+        self.cells.iter()
+    }
+
+    pub fn contains(&self, pos: &Pos) -> bool {
+        self.cells.contains(pos)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    pub fn sides(&self) -> usize {
+        self.sides
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::{Grid, Pos};
+
+    const MAZE: &str = "..#.
+.##.
+....";
+
+    #[test]
+    fn bfs() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+        let distances = super::bfs(&grid, [Pos::new(0, 0)], |_, to| *to.value != '#');
+
+        assert_eq!(distances[Pos::new(0, 0)], Some(0));
+        assert_eq!(distances[Pos::new(2, 3)], Some(5));
+        assert_eq!(distances[Pos::new(0, 3)], Some(7));
+        assert_eq!(distances[Pos::new(0, 2)], None);
+    }
+
+    #[test]
+    fn bfs_from_many_sources() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+        let distances = super::bfs(&grid, [Pos::new(0, 0), Pos::new(0, 3)], |_, to| {
+            *to.value != '#'
+        });
+
+        assert_eq!(distances[Pos::new(1, 3)], Some(1));
+        assert_eq!(distances[Pos::new(2, 1)], Some(3));
+        assert_eq!(distances[Pos::new(2, 2)], Some(3));
+    }
+
+    #[test]
+    fn bfs_with_directed_moves() {
+        let grid = "0123\n9854\n9769".parse::<Grid<u32>>().unwrap();
+        let distances = super::bfs(&grid, [Pos::new(0, 0)], |from, to| {
+            *to.value == from.value + 1
+        });
+
+        assert_eq!(distances[Pos::new(1, 3)], Some(4));
+        assert_eq!(distances[Pos::new(2, 1)], Some(7));
+        assert_eq!(distances[Pos::new(1, 0)], Some(9));
+        assert_eq!(distances[Pos::new(2, 0)], None);
+    }
+
+    #[test]
+    fn flood_fill() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+        let filled = super::flood_fill(&grid, Pos::new(0, 2), |a, b| a.value == b.value);
+        assert_eq!(filled.len(), 3);
+        assert!(filled.contains(&Pos::new(1, 1)));
+    }
+
+    #[test]
+    fn regions() {
+        let grid = "AAAA\nBBCD\nBBCC\nEEEC".parse::<Grid<char>>().unwrap();
+        let regions = super::regions(&grid);
+
+        let summary = |pos: Pos| {
+            let region = regions.iter().find(|r| r.contains(&pos)).unwrap();
+            (region.area(), region.perimeter(), region.sides())
+        };
+        assert_eq!(regions.len(), 5);
+        assert_eq!(summary(Pos::new(0, 0)), (4, 10, 4));
+        assert_eq!(summary(Pos::new(1, 0)), (4, 8, 4));
+        assert_eq!(summary(Pos::new(1, 2)), (4, 10, 8));
+        assert_eq!(summary(Pos::new(1, 3)), (1, 4, 4));
+        assert_eq!(summary(Pos::new(3, 0)), (3, 8, 4));
+    }
+
+    #[test]
+    fn region_with_hole() {
+        let grid = "OOO\nOXO\nOOO".parse::<Grid<char>>().unwrap();
+        let regions = super::regions(&grid);
+        let outer = regions
+            .iter()
+            .find(|r| r.contains(&Pos::new(0, 0)))
+            .unwrap();
+
+        assert_eq!(outer.area(), 8);
+        assert_eq!(outer.perimeter(), 16);
+        assert_eq!(outer.sides(), 8);
+    }
+}
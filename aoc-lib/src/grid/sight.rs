@@ -0,0 +1,234 @@
+use std::{cmp::Ordering, collections::BTreeMap, iter};
+
+use crate::math::gcd;
+
+use super::{Direction, Grid, Point, Pos};
+
+/// A direction on the grid given by a row and col step that have no common divisor, so that
+/// every direction has exactly one `Ray`. Rays are ordered clockwise by angle, starting with
+/// straight up.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Ray {
+    row: isize,
+    col: isize,
+}
+
+impl Ray {
+    /// The direction of the step `(row, col)`, or `None` if both are zero.
+    pub fn new(row: isize, col: isize) -> Option<Ray> {
+        let divisor = gcd(row.unsigned_abs() as u64, col.unsigned_abs() as u64) as isize;
+        (divisor != 0).then(|| Ray {
+            row: row / divisor,
+            col: col / divisor,
+        })
+    }
+
+    /// The direction from `from` to `to`, together with the number of steps along it that it
+    /// takes to get there. `None` if they are the same position.
+    pub fn between(from: Pos, to: Pos) -> Option<(Ray, usize)> {
+        let row = to.0 as isize - from.0 as isize;
+        let col = to.1 as isize - from.1 as isize;
+        let steps = gcd(row.unsigned_abs() as u64, col.unsigned_abs() as u64) as usize;
+        let ray = Ray::new(row, col)?;
+        Some((ray, steps))
+    }
+
+    pub fn delta(self) -> (isize, isize) {
+        (self.row, self.col)
+    }
+
+    /// The positions along the ray, not including `from`. Stops when the row or col would
+    /// become negative, so it has to be limited to the size of the grid.
+    pub fn cast(self, from: Pos) -> impl Iterator<Item = Pos> {
+        iter::successors(Some(from), move |pos| pos.offset(self.delta())).skip(1)
+    }
+
+    /// 0 for rays pointing up or to the right of it, 1 for everything from straight down to the
+    /// left.
+    fn half(self) -> u8 {
+        if self.col > 0 || (self.col == 0 && self.row < 0) {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl From<Direction> for Ray {
+    fn from(dir: Direction) -> Self {
+        let (row, col) = dir.delta();
+        Ray { row, col }
+    }
+}
+
+impl Ord for Ray {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Within the same half a ray comes first if the other one is clockwise from it, which is
+        // when the cross product is positive (rows grow downwards).
+        let cross = self.col * other.row - self.row * other.col;
+        self.half().cmp(&other.half()).then_with(|| 0.cmp(&cross))
+    }
+}
+
+impl PartialOrd for Ray {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The first cell after `from` along `ray` for which `blocks` is true, or `None` if the ray
+/// leaves the grid first. The topology of the grid is ignored.
+pub fn first_visible<T, F>(grid: &Grid<T>, from: Pos, ray: Ray, mut blocks: F) -> Option<Point<&T>>
+where
+    F: FnMut(&T) -> bool,
+{
+    ray.cast(from)
+        .take_while(|&pos| grid.contains(pos))
+        .find(|&pos| blocks(&grid[pos]))
+        .map(|pos| Point {
+            pos,
+            value: &grid[pos],
+        })
+}
+
+/// Groups `targets` by the ray they are on as seen from `from`, nearest first. `from` itself is
+/// left out.
+pub fn sight_lines<I>(from: Pos, targets: I) -> BTreeMap<Ray, Vec<Pos>>
+where
+    I: IntoIterator<Item = Pos>,
+{
+    let mut lines = BTreeMap::<Ray, Vec<(usize, Pos)>>::new();
+    for target in targets {
+        if let Some((ray, steps)) = Ray::between(from, target) {
+            lines.entry(ray).or_default().push((steps, target));
+        }
+    }
+    lines
+        .into_iter()
+        .map(|(ray, mut line)| {
+            line.sort_unstable_by_key(|&(steps, _)| steps);
+            (ray, line.into_iter().map(|(_, pos)| pos).collect())
+        })
+        .collect()
+}
+
+/// The targets that can be seen from `from`, which is the nearest one on every ray, in
+/// clockwise order starting with straight up.
+pub fn visible<I>(from: Pos, targets: I) -> Vec<Pos>
+where
+    I: IntoIterator<Item = Pos>,
+{
+    sight_lines(from, targets)
+        .into_values()
+        .map(|line| line[0])
+        .collect()
+}
+
+/// The order in which a beam rotating clockwise from straight up hits `targets`, if it only
+/// hits the nearest one on a ray in every turn.
+pub fn sweep<I>(from: Pos, targets: I) -> Vec<Pos>
+where
+    I: IntoIterator<Item = Pos>,
+{
+    let mut hits = sight_lines(from, targets)
+        .into_iter()
+        .flat_map(|(ray, line)| {
+            line.into_iter()
+                .enumerate()
+                .map(move |(turn, pos)| (turn, ray, pos))
+        })
+        .collect::<Vec<_>>();
+    hits.sort_unstable_by_key(|&(turn, ray, _)| (turn, ray));
+    hits.into_iter().map(|(_, _, pos)| pos).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::{Direction, Grid, Pos};
+
+    use super::Ray;
+
+    fn asteroids(input: &str) -> Vec<Pos> {
+        let grid = input.parse::<Grid<char>>().unwrap();
+        grid.iter()
+            .filter(|p| p.value == '#')
+            .map(|p| p.pos)
+            .collect()
+    }
+
+    #[test]
+    fn primitive_rays() {
+        assert_eq!(Ray::new(4, -6).unwrap().delta(), (2, -3));
+        assert_eq!(Ray::new(0, -5).unwrap(), Ray::from(Direction::Left));
+        assert_eq!(Ray::new(0, 0), None);
+        assert_eq!(
+            Ray::between(Pos::new(5, 1), Pos::new(2, 7)),
+            Some((Ray::new(-1, 2).unwrap(), 3))
+        );
+    }
+
+    #[test]
+    fn clockwise_order() {
+        let mut rays = [(1, -1), (0, 1), (-1, 0), (1, 0), (-1, -1), (-3, 1), (2, 1)]
+            .map(|(row, col)| Ray::new(row, col).unwrap());
+        rays.sort();
+        assert_eq!(
+            rays.map(Ray::delta),
+            [(-1, 0), (-3, 1), (0, 1), (2, 1), (1, 0), (1, -1), (-1, -1)]
+        );
+        let mut directions = Direction::ALL.map(Ray::from);
+        directions.sort();
+        assert_eq!(directions, Direction::ALL.map(Ray::from));
+    }
+
+    #[test]
+    fn first_visible() {
+        let grid = ".......#.\n...#.....\n.#.......\n.........\n..#L....#\n....#....\n.........\n#........\n...#....."
+            .parse::<Grid<char>>()
+            .unwrap();
+        let start = Pos::new(4, 3);
+        let seen = Direction::ALL
+            .iter()
+            .filter_map(|&dir| super::first_visible(&grid, start, dir.into(), |&c| c == '#'))
+            .count();
+        assert_eq!(seen, 8);
+
+        let seat = super::first_visible(&grid, start, Direction::Right.into(), |&c| c != '.');
+        assert_eq!(seat.map(|p| p.pos), Some(Pos::new(4, 8)));
+        assert!(
+            super::first_visible(&grid, Pos::new(0, 0), Direction::Up.into(), |_| true).is_none()
+        );
+    }
+
+    #[test]
+    fn visible() {
+        let targets = asteroids(".#..#\n.....\n#####\n....#\n...##");
+        assert_eq!(super::visible(Pos::new(4, 3), targets.clone()).len(), 8);
+        assert_eq!(super::visible(Pos::new(0, 1), targets.clone()).len(), 7);
+        let lines = super::sight_lines(Pos::new(2, 0), targets);
+        assert_eq!(
+            lines[&Ray::from(Direction::Right)],
+            [
+                Pos::new(2, 1),
+                Pos::new(2, 2),
+                Pos::new(2, 3),
+                Pos::new(2, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn sweep() {
+        let targets = asteroids(
+            ".#..##.###...#######\n##.############..##.\n.#.######.########.#\n.###.#######.####.#.\n#####.##.#.##.###.##\n..#####..#.#########\n####################\n#.####....###.#.#.##\n##.#################\n#####.##.###..####..\n..######..##.#######\n####.##.####...##..#\n.#####..#.######.###\n##...#.##########...\n#.##########.#######\n.####.#.###.###.#.##\n....##.##.###..#####\n.#.#.###########.###\n#.#.#.#####.####.###\n###.##.####.##.#..##",
+        );
+        let station = Pos::new(13, 11);
+        assert_eq!(super::visible(station, targets.clone()).len(), 210);
+        let order = super::sweep(station, targets);
+        assert_eq!(order.len(), 299);
+        assert_eq!(order[0], Pos::new(12, 11));
+        assert_eq!(order[1], Pos::new(1, 12));
+        assert_eq!(order[199], Pos::new(2, 8));
+        assert_eq!(order[298], Pos::new(1, 11));
+    }
+}
@@ -0,0 +1,284 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+};
+
+use super::{Direction, Grid, Point};
+
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug, PartialOrd, Ord)]
+pub struct SignedPos(i64, i64);
+
+impl SignedPos {
+    pub fn new(row: i64, col: i64) -> Self {
+        Self(row, col)
+    }
+    pub fn row(&self) -> i64 {
+        self.0
+    }
+    pub fn col(&self) -> i64 {
+        self.1
+    }
+
+    pub fn step(self, dir: Direction) -> Self {
+        let (row_step, col_step) = dir.delta();
+        Self(self.0 + row_step as i64, self.1 + col_step as i64)
+    }
+
+    pub fn distance_to(&self, other: &SignedPos) -> u64 {
+        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
+    }
+}
+
+/// An unbounded grid where only the cells that have been set are stored. Every other cell has
+/// the grid's default value.
+#[derive(Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<SignedPos, T>,
+    default: T,
+    bounds: Option<(SignedPos, SignedPos)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    /// Stores every cell of `grid` that differs from `default`. Row and column 0 of `grid` end
+    /// up at row and column 0 here.
+    pub fn from_grid(grid: &Grid<T>, default: T) -> Self
+    where
+        T: Clone + PartialEq,
+    {
+        let mut sparse = Self::new(default);
+        for point in grid.iter() {
+            if point.value != sparse.default {
+                let pos = SignedPos(point.pos.row() as i64, point.pos.col() as i64);
+                sparse.insert(pos, point.value);
+            }
+        }
+        sparse
+    }
+
+    /// The value of every cell that hasn't been set.
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    pub fn set_default(&mut self, default: T) {
+        self.default = default;
+    }
+
+    /// Smallest and largest row/col of all set cells, or `None` if no cell is set.
+    pub fn bounds(&self) -> Option<(SignedPos, SignedPos)> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: SignedPos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn get(&self, pos: SignedPos) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.default)
+    }
+
+    pub fn insert(&mut self, pos: SignedPos, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                SignedPos(min.0.min(pos.0), min.1.min(pos.1)),
+                SignedPos(max.0.max(pos.0), max.1.max(pos.1)),
+            ),
+            None => (pos, pos),
+        });
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: SignedPos) -> Option<T> {
+        let removed = self.cells.remove(&pos)?;
+        if let Some((min, max)) = self.bounds {
+            if pos.0 == min.0 || pos.0 == max.0 || pos.1 == min.1 || pos.1 == max.1 {
+                self.bounds = Self::compute_bounds(self.cells.keys());
+            }
+        }
+        Some(removed)
+    }
+
+    /// All set cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Point<&T, SignedPos>> {
+        self.cells.iter().map(|(&pos, value)| Point { pos, value })
+    }
+
+    pub fn neighbours4(&self, pos: SignedPos) -> impl Iterator<Item = Point<&T, SignedPos>> {
+        self.neighbours_with(pos, &Direction::ORTHOGONAL)
+    }
+
+    pub fn neighbours8(&self, pos: SignedPos) -> impl Iterator<Item = Point<&T, SignedPos>> {
+        self.neighbours_with(pos, &Direction::ALL)
+    }
+
+    pub fn neighbours_with<'a>(
+        &'a self,
+        pos: SignedPos,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = Point<&'a T, SignedPos>> + 'a {
+        directions.iter().map(move |&dir| {
+            let pos = pos.step(dir);
+            Point {
+                pos,
+                value: self.get(pos),
+            }
+        })
+    }
+
+    /// Walks from `pos` in direction `dir`, starting with `pos` itself. The path ends when it
+    /// leaves the bounding box, since every cell beyond that has the default value.
+    pub fn path(
+        &self,
+        dir: Direction,
+        pos: SignedPos,
+    ) -> impl Iterator<Item = Point<&T, SignedPos>> {
+        std::iter::successors(Some(pos), move |&pos| Some(pos.step(dir)))
+            .take_while(move |&pos| self.in_bounds(pos))
+            .map(move |pos| Point {
+                pos,
+                value: self.get(pos),
+            })
+    }
+
+    fn in_bounds(&self, pos: SignedPos) -> bool {
+        self.bounds.is_some_and(|(min, max)| {
+            (min.0..=max.0).contains(&pos.0) && (min.1..=max.1).contains(&pos.1)
+        })
+    }
+
+    fn compute_bounds<'a>(
+        mut positions: impl Iterator<Item = &'a SignedPos>,
+    ) -> Option<(SignedPos, SignedPos)> {
+        let &first = positions.next()?;
+        Some(positions.fold((first, first), |(min, max), pos| {
+            (
+                SignedPos(min.0.min(pos.0), min.1.min(pos.1)),
+                SignedPos(max.0.max(pos.0), max.1.max(pos.1)),
+            )
+        }))
+    }
+}
+
+impl<T> Debug for SparseGrid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((min, max)) = self.bounds {
+            for row in min.0..=max.0 {
+                for col in min.1..=max.1 {
+                    write!(f, "{}", self.get(SignedPos(row, col)))?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::{Direction, Grid};
+
+    use super::{SignedPos, SparseGrid};
+
+    #[test]
+    fn insert_and_bounds() {
+        let mut grid = SparseGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(SignedPos::new(-2, 3), '#');
+        grid.insert(SignedPos::new(1, -4), '#');
+        assert_eq!(
+            grid.bounds(),
+            Some((SignedPos::new(-2, -4), SignedPos::new(1, 3)))
+        );
+        assert_eq!(*grid.get(SignedPos::new(-2, 3)), '#');
+        assert_eq!(*grid.get(SignedPos::new(100, 100)), '.');
+
+        grid.remove(SignedPos::new(1, -4));
+        assert_eq!(
+            grid.bounds(),
+            Some((SignedPos::new(-2, 3), SignedPos::new(-2, 3)))
+        );
+        grid.remove(SignedPos::new(-2, 3));
+        assert_eq!(grid.bounds(), None);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn default_value() {
+        let mut grid = SparseGrid::new(false);
+        grid.insert(SignedPos::new(0, 0), true);
+        assert!(!grid.get(SignedPos::new(-1, -1)));
+
+        grid.set_default(true);
+        assert!(grid.get(SignedPos::new(-1, -1)));
+        assert_eq!(grid.len(), 1);
+    }
+
+    #[test]
+    fn from_grid() {
+        let dense = ".#.\n..#\n###".parse::<Grid<char>>().unwrap();
+        let grid = SparseGrid::from_grid(&dense, '.');
+        assert_eq!(grid.len(), 5);
+        assert_eq!(
+            grid.bounds(),
+            Some((SignedPos::new(0, 0), SignedPos::new(2, 2)))
+        );
+        assert_eq!(format!("{:?}", grid), ".#.\n..#\n###\n");
+    }
+
+    #[test]
+    fn neighbours() {
+        let mut grid = SparseGrid::new(0);
+        grid.insert(SignedPos::new(-1, 0), 1);
+        grid.insert(SignedPos::new(0, 1), 2);
+
+        assert_eq!(
+            grid.neighbours4(SignedPos::new(0, 0))
+                .map(|p| *p.value)
+                .collect::<Vec<_>>(),
+            [1, 2, 0, 0]
+        );
+        assert_eq!(
+            grid.neighbours8(SignedPos::new(0, 0))
+                .filter(|p| *p.value != 0)
+                .map(|p| p.pos)
+                .collect::<Vec<_>>(),
+            [SignedPos::new(-1, 0), SignedPos::new(0, 1)]
+        );
+    }
+
+    #[test]
+    fn path() {
+        let mut grid = SparseGrid::new('.');
+        grid.insert(SignedPos::new(0, -2), '#');
+        grid.insert(SignedPos::new(0, 2), '#');
+
+        assert_eq!(
+            grid.path(Direction::Right, SignedPos::new(0, -1))
+                .map(|p| *p.value)
+                .collect::<String>(),
+            "...#"
+        );
+        assert_eq!(grid.path(Direction::Up, SignedPos::new(0, 0)).count(), 1);
+        assert_eq!(grid.path(Direction::Up, SignedPos::new(5, 0)).count(), 0);
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::Index,
+};
+
+use super::{Grid, Point, Pos};
+
+/// One of the 8 symmetries of a rectangle. Positions in a view are mirrored first and then
+/// transposed to get the position in the underlying grid.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Orientation {
+    transpose: bool,
+    flip_rows: bool,
+    flip_cols: bool,
+}
+
+impl Orientation {
+    fn transposed(self) -> Self {
+        Self {
+            transpose: !self.transpose,
+            flip_rows: self.flip_cols,
+            flip_cols: self.flip_rows,
+        }
+    }
+}
+
+/// A rotated and/or mirrored view into a [`Grid`] that doesn't copy any cells.
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    orientation: Orientation,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    pub(super) fn new(grid: &'a Grid<T>) -> Self {
+        Self {
+            grid,
+            orientation: Orientation::default(),
+        }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    pub fn rows(&self) -> usize {
+        if self.orientation.transpose {
+            self.grid.cols()
+        } else {
+            self.grid.rows()
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        if self.orientation.transpose {
+            self.grid.rows()
+        } else {
+            self.grid.cols()
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&'a T> {
+        if pos.row() >= self.rows() || pos.col() >= self.cols() {
+            return None;
+        }
+        self.grid.get(self.source(pos))
+    }
+
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &'a T> + '_ {
+        (0..self.cols()).filter_map(move |col| self.get(Pos::new(row, col)))
+    }
+
+    pub fn col(&self, col: usize) -> impl Iterator<Item = &'a T> + '_ {
+        (0..self.rows()).filter_map(move |row| self.get(Pos::new(row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Point<&'a T>> + '_ {
+        (0..self.rows()).flat_map(move |row| {
+            (0..self.cols()).map(move |col| {
+                let pos = Pos::new(row, col);
+                Point {
+                    pos,
+                    value: &self.grid[self.source(pos)],
+                }
+            })
+        })
+    }
+
+    pub fn transpose(self) -> Self {
+        Self {
+            grid: self.grid,
+            orientation: self.orientation.transposed(),
+        }
+    }
+
+    /// Mirrors the view left to right.
+    pub fn flip_horizontal(self) -> Self {
+        let mut orientation = self.orientation;
+        orientation.flip_cols = !orientation.flip_cols;
+        Self {
+            grid: self.grid,
+            orientation,
+        }
+    }
+
+    /// Mirrors the view top to bottom.
+    pub fn flip_vertical(self) -> Self {
+        let mut orientation = self.orientation;
+        orientation.flip_rows = !orientation.flip_rows;
+        Self {
+            grid: self.grid,
+            orientation,
+        }
+    }
+
+    pub fn rotate_cw(self) -> Self {
+        self.flip_vertical().transpose()
+    }
+
+    pub fn rotate_ccw(self) -> Self {
+        self.flip_horizontal().transpose()
+    }
+
+    /// All 8 rotations and reflections of this view, starting with the view itself.
+    pub fn orientations(self) -> impl Iterator<Item = GridView<'a, T>> {
+        [false, true].into_iter().flat_map(move |flip| {
+            let start = if flip { self.flip_horizontal() } else { self };
+            std::iter::successors(Some(start), |view| Some(view.rotate_cw())).take(4)
+        })
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_rows(
+            (0..self.rows())
+                .map(|row| self.row(row).cloned().collect())
+                .collect(),
+        )
+    }
+
+    fn source(&self, pos: Pos) -> Pos {
+        let Orientation {
+            transpose,
+            flip_rows,
+            flip_cols,
+        } = self.orientation;
+        let row = if flip_rows {
+            self.rows() - 1 - pos.row()
+        } else {
+            pos.row()
+        };
+        let col = if flip_cols {
+            self.cols() - 1 - pos.col()
+        } else {
+            pos.col()
+        };
+        if transpose {
+            Pos::new(col, row)
+        } else {
+            Pos::new(row, col)
+        }
+    }
+}
+
+impl<T> Index<Pos> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "Position {:?} is outside of view with {} rows and {} cols",
+                pos,
+                self.rows(),
+                self.cols()
+            )
+        })
+    }
+}
+
+impl<T> PartialEq for GridView<'_, T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.rows() == other.rows()
+            && self.cols() == other.cols()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| a.value == b.value)
+    }
+}
+
+impl<T> Debug for GridView<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows() {
+            for value in self.row(row) {
+                write!(f, "{}", value)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::grid::{Grid, Pos};
+
+    const INPUT: &str = "abc
+def";
+
+    fn render(grid: &Grid<char>) -> String {
+        format!("{:?}", grid)
+    }
+
+    #[test]
+    fn transpose() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+        let view = grid.transpose();
+        assert_eq!((view.rows(), view.cols()), (3, 2));
+        assert_eq!(render(&view.to_grid()), "ad\nbe\ncf\n");
+        assert_eq!(render(&view.transpose().to_grid()), "abc\ndef\n");
+    }
+
+    #[test]
+    fn rotate() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+        assert_eq!(render(&grid.rotate_cw().to_grid()), "da\neb\nfc\n");
+        assert_eq!(render(&grid.rotate_ccw().to_grid()), "cf\nbe\nad\n");
+        assert_eq!(
+            render(&grid.rotate_cw().rotate_cw().to_grid()),
+            "fed\ncba\n"
+        );
+        assert!(grid.rotate_cw().rotate_ccw() == grid.view());
+        assert!(grid.rotate_cw().rotate_cw().rotate_cw() == grid.rotate_ccw());
+    }
+
+    #[test]
+    fn flip() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+        assert_eq!(render(&grid.flip_horizontal().to_grid()), "cba\nfed\n");
+        assert_eq!(render(&grid.flip_vertical().to_grid()), "def\nabc\n");
+        assert_eq!(grid.flip_vertical()[Pos::new(0, 2)], 'f');
+        assert_eq!(grid.flip_vertical().get(Pos::new(2, 0)), None);
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+        let view = grid.rotate_cw();
+        assert_eq!(view.row(1).collect::<String>(), "eb");
+        assert_eq!(view.col(1).collect::<String>(), "abc");
+    }
+
+    #[test]
+    fn orientations() {
+        let grid = INPUT.parse::<Grid<char>>().unwrap();
+        let all = grid
+            .orientations()
+            .map(|view| render(&view.to_grid()))
+            .collect::<HashSet<_>>();
+        assert_eq!(all.len(), 8);
+        assert_eq!(
+            grid.orientations()
+                .map(|view| view.orientation())
+                .collect::<HashSet<_>>()
+                .len(),
+            8
+        );
+
+        let symmetric = "ab\nba".parse::<Grid<char>>().unwrap();
+        let all = symmetric
+            .orientations()
+            .map(|view| render(&view.to_grid()))
+            .collect::<HashSet<_>>();
+        assert_eq!(all.len(), 2);
+    }
+}
@@ -0,0 +1,285 @@
+use std::{collections::HashMap, str::FromStr};
+
+/// The six neighbours of a hexagon in a grid of pointy-topped hexagons, where rows of hexagons
+/// run east to west.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    /// Parses a string of directions without separators, e.g. `"esenee"`.
+    pub fn parse_path(s: &str) -> Result<Vec<HexDirection>, String> {
+        let mut path = Vec::new();
+        let mut rest = s.trim();
+        while !rest.is_empty() {
+            let len = if rest.starts_with(['n', 's']) { 2 } else { 1 };
+            let step = rest
+                .get(..len)
+                .ok_or_else(|| format!("Incomplete direction at end of {}", s))?;
+            path.push(step.parse()?);
+            rest = &rest[len..];
+        }
+        Ok(path)
+    }
+
+    fn delta(self) -> (i64, i64) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::SouthEast => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::West => (-1, 0),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::NorthEast => (1, -1),
+        }
+    }
+}
+
+impl FromStr for HexDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "e" => Ok(HexDirection::East),
+            "se" => Ok(HexDirection::SouthEast),
+            "sw" => Ok(HexDirection::SouthWest),
+            "w" => Ok(HexDirection::West),
+            "nw" => Ok(HexDirection::NorthWest),
+            "ne" => Ok(HexDirection::NorthEast),
+            _ => Err(format!("Unknown hex direction {}", s)),
+        }
+    }
+}
+
+/// A hexagon in axial coordinates. The implicit third cube coordinate is `s = -q - r`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default, PartialOrd, Ord)]
+pub struct Hex {
+    q: i64,
+    r: i64,
+}
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex { q: 0, r: 0 };
+
+    pub fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    /// Cube coordinates always sum to zero, anything else is not a hexagon.
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Option<Self> {
+        (q + r + s == 0).then_some(Self { q, r })
+    }
+
+    pub fn q(&self) -> i64 {
+        self.q
+    }
+
+    pub fn r(&self) -> i64 {
+        self.r
+    }
+
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub fn step(self, dir: HexDirection) -> Hex {
+        let (q, r) = dir.delta();
+        Hex {
+            q: self.q + q,
+            r: self.r + r,
+        }
+    }
+
+    /// Follows all steps of `path`, starting from `self`.
+    pub fn walk<'a, I>(self, path: I) -> Hex
+    where
+        I: IntoIterator<Item = &'a HexDirection>,
+    {
+        path.into_iter().fold(self, |hex, &dir| hex.step(dir))
+    }
+
+    pub fn neighbours(self) -> impl Iterator<Item = Hex> {
+        HexDirection::ALL.into_iter().map(move |dir| self.step(dir))
+    }
+
+    /// Number of steps needed to get from `self` to `other`.
+    pub fn distance_to(&self, other: &Hex) -> u64 {
+        (self.q.abs_diff(other.q) + self.r.abs_diff(other.r) + self.s().abs_diff(other.s())) / 2
+    }
+
+    /// All hexagons exactly `radius` steps away, going clockwise. A radius of 0 is just `self`.
+    pub fn ring(self, radius: u64) -> impl Iterator<Item = Hex> {
+        let start = (0..radius).fold(self, |hex, _| hex.step(HexDirection::NorthWest));
+        let sides = if radius == 0 { 1 } else { 6 };
+        let steps = radius.max(1);
+        HexDirection::ALL
+            .into_iter()
+            .take(sides)
+            .flat_map(move |dir| std::iter::repeat_n(dir, steps as usize))
+            .scan(start, move |hex, dir| {
+                let current = *hex;
+                *hex = hex.step(dir);
+                Some(current)
+            })
+    }
+}
+
+/// An unbounded map of hexagons where only the hexagons that have been set are stored.
+pub struct HexMap<T> {
+    cells: HashMap<Hex, T>,
+    default: T,
+}
+
+impl<T> Clone for HexMap<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cells: self.cells.clone(),
+            default: self.default.clone(),
+        }
+    }
+}
+
+impl<T> HexMap<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+        }
+    }
+
+    /// The value of every hexagon that hasn't been set.
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, hex: Hex) -> bool {
+        self.cells.contains_key(&hex)
+    }
+
+    pub fn get(&self, hex: Hex) -> &T {
+        self.cells.get(&hex).unwrap_or(&self.default)
+    }
+
+    pub fn insert(&mut self, hex: Hex, value: T) -> Option<T> {
+        self.cells.insert(hex, value)
+    }
+
+    pub fn remove(&mut self, hex: Hex) -> Option<T> {
+        self.cells.remove(&hex)
+    }
+
+    /// All hexagons that have been set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Hex, &T)> {
+        self.cells.iter().map(|(&hex, value)| (hex, value))
+    }
+
+    pub fn neighbours(&self, hex: Hex) -> impl Iterator<Item = (Hex, &T)> {
+        hex.neighbours().map(move |n| (n, self.get(n)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{Hex, HexDirection, HexMap};
+
+    #[test]
+    fn parse_path() {
+        assert_eq!(
+            HexDirection::parse_path("esenee").unwrap(),
+            [
+                HexDirection::East,
+                HexDirection::SouthEast,
+                HexDirection::NorthEast,
+                HexDirection::East
+            ]
+        );
+        assert!(HexDirection::parse_path("ex").is_err());
+        assert!(HexDirection::parse_path("en").is_err());
+    }
+
+    #[test]
+    fn walk() {
+        let path = HexDirection::parse_path("nwwswee").unwrap();
+        assert_eq!(Hex::ORIGIN.walk(&path), Hex::ORIGIN);
+
+        let path = HexDirection::parse_path("esew").unwrap();
+        assert_eq!(
+            Hex::ORIGIN.walk(&path),
+            Hex::ORIGIN.step(HexDirection::SouthEast)
+        );
+    }
+
+    #[test]
+    fn cube_coordinates() {
+        let hex = Hex::new(2, -5);
+        assert_eq!(hex.s(), 3);
+        assert_eq!(Hex::from_cube(2, -5, 3), Some(hex));
+        assert_eq!(Hex::from_cube(2, -5, 2), None);
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(Hex::ORIGIN.distance_to(&Hex::ORIGIN), 0);
+        for n in Hex::ORIGIN.neighbours() {
+            assert_eq!(Hex::ORIGIN.distance_to(&n), 1);
+        }
+        let far = Hex::ORIGIN.walk(&HexDirection::parse_path("eeenenw").unwrap());
+        assert_eq!(Hex::ORIGIN.distance_to(&far), 4);
+        assert_eq!(far.distance_to(&Hex::ORIGIN), 4);
+    }
+
+    #[test]
+    fn ring() {
+        let center = Hex::new(3, -1);
+        assert_eq!(center.ring(0).collect::<Vec<_>>(), [center]);
+        assert_eq!(
+            center.ring(1).collect::<HashSet<_>>(),
+            center.neighbours().collect::<HashSet<_>>()
+        );
+
+        let ring = center.ring(3).collect::<Vec<_>>();
+        assert_eq!(ring.len(), 18);
+        assert_eq!(ring.iter().collect::<HashSet<_>>().len(), 18);
+        assert!(ring.iter().all(|h| h.distance_to(&center) == 3));
+    }
+
+    #[test]
+    fn hex_map() {
+        let mut map = HexMap::new(false);
+        map.insert(Hex::ORIGIN, true);
+        assert!(*map.get(Hex::ORIGIN));
+        assert!(!map.get(Hex::new(1, 0)));
+
+        let east = Hex::ORIGIN.step(HexDirection::East);
+        assert_eq!(map.neighbours(east).filter(|(_, &black)| black).count(), 1);
+        assert_eq!(map.remove(Hex::ORIGIN), Some(true));
+        assert!(map.is_empty());
+    }
+}
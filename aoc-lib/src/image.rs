@@ -0,0 +1,290 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    grid::Grid,
+    point::{BoundingBox, Point2},
+};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+}
+
+/// The file formats an [`Image`] can be written in. Both are the binary variants of the Netpbm
+/// formats, which most image viewers can open.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Format {
+    /// Black and white. Every pixel that isn't [`Rgb::WHITE`] is written as black.
+    Pbm,
+    /// Full colour.
+    Ppm,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Pbm => "pbm",
+            Format::Ppm => "ppm",
+        }
+    }
+}
+
+/// A picture of a grid or a set of points, for looking at a puzzle state in an image viewer
+/// rather than in the terminal.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// One pixel per cell, coloured by `colour`.
+    pub fn from_grid<T, F>(grid: &Grid<T>, mut colour: F) -> Self
+    where
+        F: FnMut(&T) -> Rgb,
+    {
+        let mut image = Self::new(grid.cols(), grid.rows(), Rgb::WHITE);
+        for point in grid.view().iter() {
+            image.set(point.pos.col(), point.pos.row(), colour(point.value));
+        }
+        image
+    }
+
+    /// One pixel per point in the bounding box of `points`, where the first coordinate is `x`
+    /// and the second is `y`. Points are drawn in `foreground`, everything else in `background`.
+    pub fn from_points<I>(points: I, foreground: Rgb, background: Rgb) -> Self
+    where
+        I: IntoIterator<Item = Point2>,
+    {
+        let points = points.into_iter().collect::<Vec<_>>();
+        let Some(bounds) = BoundingBox::from_points(points.iter().copied()) else {
+            return Self::new(0, 0, background);
+        };
+        let min = bounds.min();
+        let size = bounds.max() - min;
+        let mut image = Self::new(size[0] as usize + 1, size[1] as usize + 1, background);
+        for point in points {
+            let offset = point - min;
+            image.set(offset[0] as usize, offset[1] as usize, foreground);
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Panics if the pixel is outside of the image.
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        assert!(
+            x < self.width && y < self.height,
+            "Pixel ({}, {}) is outside of image with width {} and height {}",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    /// Every pixel becomes a `factor` by `factor` square, since one pixel per cell is often too
+    /// small to see anything.
+    pub fn scale(&self, factor: usize) -> Self {
+        let mut scaled = Self::new(self.width * factor, self.height * factor, Rgb::WHITE);
+        for y in 0..scaled.height {
+            for x in 0..scaled.width {
+                scaled.pixels[y * scaled.width + x] =
+                    self.pixels[(y / factor) * self.width + x / factor];
+            }
+        }
+        scaled
+    }
+
+    pub fn write<W: Write>(&self, format: Format, out: W) -> io::Result<()> {
+        match format {
+            Format::Pbm => self.write_pbm(out),
+            Format::Ppm => self.write_ppm(out),
+        }
+    }
+
+    pub fn write_pbm<W: Write>(&self, mut out: W) -> io::Result<()> {
+        write!(out, "P4\n{} {}\n", self.width, self.height)?;
+        for row in self.pixels.chunks(self.width.max(1)) {
+            // Every row starts on a new byte, the most significant bit is the leftmost pixel.
+            for byte in row.chunks(8) {
+                let bits = byte
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &p)| p != Rgb::WHITE)
+                    .fold(0u8, |bits, (i, _)| bits | (0x80 >> i));
+                out.write_all(&[bits])?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write_ppm<W: Write>(&self, mut out: W) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        for &Rgb(r, g, b) in &self.pixels {
+            out.write_all(&[r, g, b])?;
+        }
+        Ok(())
+    }
+
+    /// Writes the image to `path`, in the format matching its extension.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let format = match path.extension().and_then(|e| e.to_str()) {
+            Some("pbm") => Format::Pbm,
+            Some("ppm") => Format::Ppm,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unknown image format for {}", path.display()),
+                ))
+            }
+        };
+        // Flushing here rather than on drop, which would ignore a failed final write.
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(format, &mut out)?;
+        out.flush()
+    }
+}
+
+/// Writes numbered images to a directory, e.g. `frame_00000.ppm`, `frame_00001.ppm` and so on,
+/// one for every step of a simulation.
+pub struct FrameWriter {
+    dir: PathBuf,
+    prefix: String,
+    format: Format,
+    next: usize,
+}
+
+impl FrameWriter {
+    /// Creates `dir` if it doesn't exist yet. Existing frames with the same prefix are
+    /// overwritten.
+    pub fn new<P: AsRef<Path>>(dir: P, prefix: &str, format: Format) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            prefix: prefix.to_string(),
+            format,
+            next: 0,
+        })
+    }
+
+    /// Number of frames written so far.
+    pub fn frames(&self) -> usize {
+        self.next
+    }
+
+    /// Writes the next frame and returns the path it was written to.
+    pub fn write(&mut self, image: &Image) -> io::Result<PathBuf> {
+        let path = self.dir.join(format!(
+            "{}_{:05}.{}",
+            self.prefix,
+            self.next,
+            self.format.extension()
+        ));
+        let mut out = BufWriter::new(File::create(&path)?);
+        image.write(self.format, &mut out)?;
+        out.flush()?;
+        self.next += 1;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::{grid::Grid, point::Point2};
+
+    use super::{Format, FrameWriter, Image, Rgb};
+
+    fn grid() -> Image {
+        let grid = "#..\n.#.".parse::<Grid<char>>().unwrap();
+        Image::from_grid(&grid, |&c| if c == '#' { Rgb::BLACK } else { Rgb::WHITE })
+    }
+
+    #[test]
+    fn pbm() {
+        let mut out = Vec::new();
+        grid().write_pbm(&mut out).unwrap();
+        assert_eq!(out, b"P4\n3 2\n\x80\x40");
+
+        let wide = Image::new(9, 1, Rgb::BLACK);
+        let mut out = Vec::new();
+        wide.write_pbm(&mut out).unwrap();
+        assert_eq!(out, b"P4\n9 1\n\xff\x80");
+    }
+
+    #[test]
+    fn ppm() {
+        let mut image = Image::new(2, 1, Rgb::WHITE);
+        image.set(1, 0, Rgb(1, 2, 3));
+        let mut out = Vec::new();
+        image.write_ppm(&mut out).unwrap();
+        assert_eq!(out, b"P6\n2 1\n255\n\xff\xff\xff\x01\x02\x03");
+    }
+
+    #[test]
+    fn from_points() {
+        let image = Image::from_points(
+            [Point2::new([-1, 5]), Point2::new([1, 6])],
+            Rgb::BLACK,
+            Rgb::WHITE,
+        );
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.get(0, 0), Some(Rgb::BLACK));
+        assert_eq!(image.get(2, 1), Some(Rgb::BLACK));
+        assert_eq!(image.get(1, 0), Some(Rgb::WHITE));
+        assert_eq!(image.get(3, 0), None);
+    }
+
+    #[test]
+    fn scale() {
+        let image = grid().scale(2);
+        assert_eq!((image.width(), image.height()), (6, 4));
+        assert_eq!(image.get(1, 1), Some(Rgb::BLACK));
+        assert_eq!(image.get(2, 1), Some(Rgb::WHITE));
+        assert_eq!(image.get(3, 3), Some(Rgb::BLACK));
+    }
+
+    #[test]
+    fn frames() {
+        let dir = std::env::temp_dir().join(format!("aoc_lib_frames_{}", std::process::id()));
+        let mut frames = FrameWriter::new(&dir, "step", Format::Pbm).unwrap();
+        frames.write(&grid()).unwrap();
+        let last = frames.write(&grid()).unwrap();
+        assert_eq!(frames.frames(), 2);
+        assert_eq!(last, dir.join("step_00001.pbm"));
+        assert_eq!(fs::read(&last).unwrap(), b"P4\n3 2\n\x80\x40");
+        assert!(grid().save(dir.join("image.png")).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, Bound, Range, RangeBounds, Sub},
+};
+
+/// Integer types that an [`IntervalSet`] can hold.
+pub trait Integer: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+        })*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// A set of integers stored as sorted ranges that don't overlap or touch, so every set has
+/// exactly one representation. Methods take any bounded range, e.g. `3..7` or `3..=6`, and the
+/// ranges that come out are always half-open.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |len, r| len + (r.end - r.start))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of separate ranges in the set.
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn ranges(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().cloned()
+    }
+
+    /// The ranges between the ranges of the set, from the lowest to the highest value in it.
+    pub fn gaps(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.windows(2).map(|w| w[0].end..w[1].start)
+    }
+
+    pub fn first(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn last(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end - T::ONE)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    /// Whether every integer in `range` is in the set.
+    pub fn contains_range<R: RangeBounds<T>>(&self, range: R) -> bool {
+        let range = half_open(range);
+        if range.is_empty() {
+            return true;
+        }
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    pub fn insert<R: RangeBounds<T>>(&mut self, range: R) {
+        let Range { mut start, mut end } = half_open(range);
+        if start >= end {
+            return;
+        }
+        // Ranges that overlap or touch the new one are merged into it.
+        let first = self.ranges.partition_point(|r| r.end < start);
+        let last = self.ranges.partition_point(|r| r.start <= end);
+        if first < last {
+            start = start.min(self.ranges[first].start);
+            end = end.max(self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, [start..end]);
+    }
+
+    pub fn remove<R: RangeBounds<T>>(&mut self, range: R) {
+        let Range { start, end } = half_open(range);
+        if start >= end {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= start);
+        let last = self.ranges.partition_point(|r| r.start < end);
+        if first == last {
+            return;
+        }
+        let left = self.ranges[first].start..start;
+        let right = end..self.ranges[last - 1].end;
+        self.ranges.splice(
+            first..last,
+            [left, right].into_iter().filter(|r| !r.is_empty()),
+        );
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut union = self.clone();
+        union.extend(other.ranges());
+        union
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// The integers that are in `self` but not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut difference = self.clone();
+        for range in other.ranges() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// Moves every value in the source range of a piece by the same offset, so that the start of
+    /// the source ends up at `destination`. Values outside of every piece stay where they are. If
+    /// pieces overlap, the first one wins.
+    pub fn map_piecewise<R, I>(&self, pieces: I) -> IntervalSet<T>
+    where
+        R: RangeBounds<T>,
+        I: IntoIterator<Item = (R, T)>,
+    {
+        let mut unmapped = self.clone();
+        let mut mapped = IntervalSet::new();
+        for (source, destination) in pieces {
+            let source = IntervalSet::from_iter([source]);
+            let Some(source_start) = source.first() else {
+                continue;
+            };
+            for range in unmapped.intersection(&source).ranges() {
+                mapped.insert(
+                    destination + (range.start - source_start)
+                        ..destination + (range.end - source_start),
+                );
+            }
+            unmapped = unmapped.difference(&source);
+        }
+        mapped.union(&unmapped)
+    }
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer, R: RangeBounds<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Integer, R: RangeBounds<T>> Extend<R> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+/// Panics for ranges without a start or end, since there's no sensible value to use for them,
+/// and for ranges that include the largest value of `T`, since they have no half-open end.
+fn half_open<T: Integer, R: RangeBounds<T>>(range: R) -> Range<T> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => one_past(start),
+        Bound::Unbounded => panic!("Interval sets can only hold bounded ranges"),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => one_past(end),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => panic!("Interval sets can only hold bounded ranges"),
+    };
+    start..end
+}
+
+fn one_past<T: Integer>(n: T) -> T {
+    n.checked_add(T::ONE).unwrap_or_else(|| {
+        panic!(
+            "Interval sets can't hold ranges up to and including {:?}, the largest value",
+            n
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::IntervalSet;
+
+    fn ranges(set: &IntervalSet<i64>) -> Vec<(i64, i64)> {
+        set.ranges().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn insert() {
+        let mut set = IntervalSet::new();
+        set.insert(10..20);
+        set.insert(30..=39);
+        set.insert(0..5);
+        assert_eq!(ranges(&set), [(0, 5), (10, 20), (30, 40)]);
+        set.insert(5..7);
+        set.insert(15..32);
+        assert_eq!(ranges(&set), [(0, 7), (10, 40)]);
+        set.insert(3..3);
+        set.insert(-5..100);
+        assert_eq!(ranges(&set), [(-5, 100)]);
+        assert_eq!(set.len(), 105);
+    }
+
+    #[test]
+    fn remove() {
+        let mut set = IntervalSet::from_iter([0..10, 20..30, 40..50]);
+        set.remove(5..=24);
+        assert_eq!(ranges(&set), [(0, 5), (25, 30), (40, 50)]);
+        set.remove(40..50);
+        set.remove(0..1);
+        set.remove(100..200);
+        assert_eq!(ranges(&set), [(1, 5), (25, 30)]);
+        set.remove(2..3);
+        assert_eq!(ranges(&set), [(1, 2), (3, 5), (25, 30)]);
+        assert_eq!(set.len(), 8);
+        assert_eq!(set.range_count(), 3);
+    }
+
+    #[test]
+    fn queries() {
+        let set = IntervalSet::from_iter([3..=5, 10..=20]);
+        assert!(set.contains(3));
+        assert!(set.contains(20));
+        assert!(!set.contains(6));
+        assert!(!set.contains(21));
+        assert!(set.contains_range(12..15));
+        assert!(!set.contains_range(4..12));
+        assert_eq!((set.first(), set.last()), (Some(3), Some(20)));
+        assert_eq!(set.gaps().next(), Some(6..10));
+        assert_eq!(set.gaps().count(), 1);
+        assert!(IntervalSet::<u32>::new().is_empty());
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = IntervalSet::from_iter([0..10, 20..30]);
+        let b = IntervalSet::from_iter([5..25, 28..40]);
+        assert_eq!(ranges(&a.union(&b)), [(0, 40)]);
+        assert_eq!(ranges(&a.intersection(&b)), [(5, 10), (20, 25), (28, 30)]);
+        assert_eq!(ranges(&a.difference(&b)), [(0, 5), (25, 28)]);
+        assert_eq!(ranges(&b.difference(&a)), [(10, 20), (30, 40)]);
+    }
+
+    #[test]
+    fn map_piecewise() {
+        // The seed-to-soil map of 2023 day 5.
+        let seeds = IntervalSet::from_iter([79..79 + 14, 55..55 + 13]);
+        let soil = seeds.map_piecewise([(98..100, 50), (50..98, 52)]);
+        assert_eq!(ranges(&soil), [(57, 70), (81, 95)]);
+
+        let mut set = IntervalSet::new();
+        set.insert(0..10);
+        let moved = set.map_piecewise([(2..4, 100), (3..5, -10)]);
+        assert_eq!(ranges(&moved), [(-9, -8), (0, 2), (5, 10), (100, 102)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the largest value")]
+    fn inclusive_up_to_max() {
+        IntervalSet::<u8>::from_iter([0..=255]);
+    }
+}
@@ -1,2 +1,12 @@
+pub mod automaton;
+pub mod cycle;
+pub mod geometry;
 pub mod graph;
 pub mod grid;
+pub mod hex;
+pub mod image;
+pub mod interval;
+pub mod math;
+pub mod ocr;
+pub mod point;
+pub mod shortest_path;
@@ -0,0 +1,18 @@
+/// Greatest common divisor, where `gcd(0, 0)` is 0.
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn gcd() {
+        assert_eq!(super::gcd(12, 18), 6);
+        assert_eq!(super::gcd(7, 0), 7);
+        assert_eq!(super::gcd(0, 0), 0);
+    }
+}
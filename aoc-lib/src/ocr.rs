@@ -0,0 +1,266 @@
+use std::fmt::Display;
+
+use crate::{
+    grid::{Grid, Pos},
+    point::{BoundingBox, Point2},
+};
+
+/// The font used by most puzzles, with letters that are 6 pixels high and usually 4 wide.
+const SMALL: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The font of 2018 day 10, with letters that are 10 pixels high and 6 wide.
+const LARGE: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum OcrError {
+    /// Letters are 6 or 10 pixels high, depending on the font.
+    UnsupportedHeight(usize),
+    /// The letter at `index` (counting from 0) isn't part of the font. `glyph` is the letter
+    /// drawn with `#` and `.`.
+    UnknownGlyph { index: usize, glyph: String },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::UnsupportedHeight(height) => {
+                write!(f, "Text is {} pixels high, expected 6 or 10", height)
+            }
+            OcrError::UnknownGlyph { index, glyph } => {
+                write!(f, "Unknown letter at index {}:\n{}", index, glyph)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads the capital letters drawn by the lit cells of `grid`. Empty rows and columns around the
+/// text are ignored.
+pub fn recognize(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let lit = (0..grid.rows())
+        .flat_map(|row| (0..grid.cols()).map(move |col| Pos::new(row, col)))
+        .filter(|&pos| grid[pos])
+        .map(|pos| Point2::new([pos.col() as i64, pos.row() as i64]));
+    recognize_points(lit)
+}
+
+/// Like [`recognize`], for a set of lit points where the first coordinate is `x` and the second
+/// is `y`.
+pub fn recognize_points<I>(points: I) -> Result<String, OcrError>
+where
+    I: IntoIterator<Item = Point2>,
+{
+    let points = points.into_iter().collect::<Vec<_>>();
+    let Some(bounds) = BoundingBox::from_points(points.iter().copied()) else {
+        return Ok(String::new());
+    };
+    let (min, max) = (bounds.min(), bounds.max());
+    let width = (max[0] - min[0]) as usize + 1;
+    let height = (max[1] - min[1]) as usize + 1;
+    let font: &[(char, &str)] = match height {
+        6 => &SMALL,
+        10 => &LARGE,
+        _ => return Err(OcrError::UnsupportedHeight(height)),
+    };
+
+    let mut pixels = vec![vec![false; width]; height];
+    for point in points {
+        pixels[(point[1] - min[1]) as usize][(point[0] - min[0]) as usize] = true;
+    }
+
+    // Letters are separated by at least one empty column and have none inside them.
+    let mut text = String::new();
+    let mut col = 0;
+    while col < width {
+        if (0..height).all(|row| !pixels[row][col]) {
+            col += 1;
+            continue;
+        }
+        let start = col;
+        while col < width && (0..height).any(|row| pixels[row][col]) {
+            col += 1;
+        }
+        let glyph = pixels
+            .iter()
+            .map(|row| {
+                row[start..col]
+                    .iter()
+                    .map(|&lit| if lit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let letter = font
+            .iter()
+            .find(|(_, drawn)| *drawn == glyph)
+            .map(|&(letter, _)| letter)
+            .ok_or_else(|| OcrError::UnknownGlyph {
+                index: text.chars().count(),
+                glyph,
+            })?;
+        text.push(letter);
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{grid::Grid, point::Point2};
+
+    use super::{OcrError, LARGE, SMALL};
+
+    fn parse(s: &str) -> Grid<bool> {
+        s.parse::<Grid<char>>().unwrap().map(|&c| c == '#')
+    }
+
+    /// Draws `font` letters next to each other with `gap` empty columns in between.
+    fn draw(font: &[(char, &str)], gap: usize) -> String {
+        let glyphs = font
+            .iter()
+            .map(|(_, g)| g.lines().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        (0..glyphs[0].len())
+            .map(|row| {
+                glyphs
+                    .iter()
+                    .map(|g| g[row].to_string())
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn small_font() {
+        let text = draw(&SMALL, 1);
+        assert_eq!(
+            super::recognize(&parse(&text)).unwrap(),
+            "ABCEFGHIJKLOPRSUYZ"
+        );
+    }
+
+    #[test]
+    fn large_font() {
+        let text = draw(&LARGE, 2);
+        assert_eq!(super::recognize(&parse(&text)).unwrap(), "ABCEFGHJKLNPRXZ");
+    }
+
+    #[test]
+    fn padding_is_ignored() {
+        let grid = parse("......\n.#..#.\n.#..#.\n.####.\n.#..#.\n.#..#.\n.#..#.\n......\n......");
+        assert_eq!(super::recognize(&grid).unwrap(), "H");
+        assert_eq!(super::recognize(&parse("...\n...")).unwrap(), "");
+    }
+
+    #[test]
+    fn points() {
+        let points = "#..#\n#..#\n####\n#..#\n#..#\n#..#"
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c == '#')
+                    .map(move |(x, _)| Point2::new([x as i64 - 10, y as i64 + 3]))
+            });
+        assert_eq!(super::recognize_points(points).unwrap(), "H");
+    }
+
+    #[test]
+    fn errors() {
+        let grid = parse("#..#.####\n#..#.#..#\n####.#..#\n#..#.#..#\n#..#.#..#\n#..#.####");
+        assert_eq!(
+            super::recognize(&grid),
+            Err(OcrError::UnknownGlyph {
+                index: 1,
+                glyph: "####\n#..#\n#..#\n#..#\n#..#\n####".to_string()
+            })
+        );
+        assert_eq!(
+            super::recognize(&parse("#\n#")),
+            Err(OcrError::UnsupportedHeight(2))
+        );
+    }
+}
@@ -0,0 +1,444 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    hash::{Hash, Hasher},
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// A point (or vector) with `D` signed integer coordinates.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, PartialOrd, Ord)]
+pub struct PointN<const D: usize>([i64; D]);
+
+pub type Point2 = PointN<2>;
+pub type Point3 = PointN<3>;
+pub type Point4 = PointN<4>;
+
+impl<const D: usize> PointN<D> {
+    pub const ORIGIN: PointN<D> = PointN([0; D]);
+
+    pub fn new(coords: [i64; D]) -> Self {
+        Self(coords)
+    }
+
+    pub fn coords(&self) -> [i64; D] {
+        self.0
+    }
+
+    /// The point that is `value` along `axis` and 0 on every other axis.
+    pub fn unit(axis: usize, value: i64) -> Self {
+        let mut coords = [0; D];
+        coords[axis] = value;
+        Self(coords)
+    }
+
+    pub fn manhattan_distance(&self, other: &PointN<D>) -> u64 {
+        (0..D).map(|i| self.0[i].abs_diff(other.0[i])).sum()
+    }
+
+    /// Euclidean distance squared, which keeps everything in integers and still sorts the same.
+    pub fn squared_distance(&self, other: &PointN<D>) -> u64 {
+        (0..D).map(|i| self.0[i].abs_diff(other.0[i]).pow(2)).sum()
+    }
+
+    /// The `2 * D` points that differ by one in exactly one coordinate.
+    pub fn von_neumann_neighbours(self) -> impl Iterator<Item = PointN<D>> {
+        (0..D).flat_map(move |axis| [-1, 1].map(|step| self + Self::unit(axis, step)))
+    }
+
+    /// The `3^D - 1` points that differ by at most one in every coordinate.
+    pub fn moore_neighbours(self) -> impl Iterator<Item = PointN<D>> {
+        let count = 3usize.pow(D as u32);
+        (0..count)
+            .filter(move |&i| i != count / 2)
+            .map(move |mut i| {
+                let mut offset = [0; D];
+                for coord in offset.iter_mut() {
+                    *coord = (i % 3) as i64 - 1;
+                    i /= 3;
+                }
+                self + PointN(offset)
+            })
+    }
+}
+
+impl<const D: usize> Default for PointN<D> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const D: usize> From<[i64; D]> for PointN<D> {
+    fn from(coords: [i64; D]) -> Self {
+        Self(coords)
+    }
+}
+
+impl<const D: usize> Index<usize> for PointN<D> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &Self::Output {
+        &self.0[axis]
+    }
+}
+
+impl<const D: usize> IndexMut<usize> for PointN<D> {
+    fn index_mut(&mut self, axis: usize) -> &mut Self::Output {
+        &mut self.0[axis]
+    }
+}
+
+impl<const D: usize> Add for PointN<D> {
+    type Output = PointN<D>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<const D: usize> AddAssign for PointN<D> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const D: usize> Sub for PointN<D> {
+    type Output = PointN<D>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<const D: usize> SubAssign for PointN<D> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const D: usize> Neg for PointN<D> {
+    type Output = PointN<D>;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+impl<const D: usize> Mul<i64> for PointN<D> {
+    type Output = PointN<D>;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Self(self.0.map(|c| c * rhs))
+    }
+}
+
+/// Parses comma separated coordinates, e.g. `"1,-2,3"`. Whitespace around a coordinate is
+/// ignored.
+impl<const D: usize> FromStr for PointN<D> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut coords = [0; D];
+        let mut parts = s.trim().split(',');
+        for coord in coords.iter_mut() {
+            let part = parts
+                .next()
+                .ok_or_else(|| format!("Expected {} coordinates in {}", D, s))?;
+            *coord = part
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid coordinate {} in {}", part, s))?;
+        }
+        if parts.next().is_some() {
+            return Err(format!("Expected {} coordinates in {}", D, s));
+        }
+        Ok(Self(coords))
+    }
+}
+
+/// Axis aligned box between two corners, both inclusive.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct BoundingBox<const D: usize> {
+    min: PointN<D>,
+    max: PointN<D>,
+}
+
+impl<const D: usize> BoundingBox<D> {
+    /// The smallest box containing every point, or `None` if there are no points.
+    pub fn from_points<I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = PointN<D>>,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let mut bounds = Self {
+            min: first,
+            max: first,
+        };
+        for point in points {
+            bounds.include(point);
+        }
+        Some(bounds)
+    }
+
+    pub fn min(&self) -> PointN<D> {
+        self.min
+    }
+
+    pub fn max(&self) -> PointN<D> {
+        self.max
+    }
+
+    /// Grows the box just enough to contain `point`.
+    pub fn include(&mut self, point: PointN<D>) {
+        for i in 0..D {
+            self.min.0[i] = self.min.0[i].min(point.0[i]);
+            self.max.0[i] = self.max.0[i].max(point.0[i]);
+        }
+    }
+
+    /// The box grown by `margin` in every direction.
+    pub fn expand(&self, margin: i64) -> Self {
+        Self {
+            min: self.min - PointN([margin; D]),
+            max: self.max + PointN([margin; D]),
+        }
+    }
+
+    pub fn contains(&self, point: PointN<D>) -> bool {
+        (0..D).all(|i| (self.min.0[i]..=self.max.0[i]).contains(&point.0[i]))
+    }
+
+    /// Number of points inside the box.
+    pub fn volume(&self) -> u64 {
+        (0..D)
+            .map(|i| self.max.0[i].abs_diff(self.min.0[i]) + 1)
+            .product()
+    }
+
+    /// Every point inside the box, with the first coordinate changing fastest.
+    pub fn iter(&self) -> impl Iterator<Item = PointN<D>> {
+        let Self { min, max } = *self;
+        std::iter::successors(Some(min), move |&point| {
+            let mut next = point;
+            for i in 0..D {
+                if next.0[i] < max.0[i] {
+                    next.0[i] += 1;
+                    return Some(next);
+                }
+                next.0[i] = min.0[i];
+            }
+            None
+        })
+    }
+}
+
+/// A sparse set of points, e.g. the voxels of a droplet or the active cubes of a 3D game of
+/// life.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct PointSet<const D: usize> {
+    points: HashSet<PointN<D>>,
+}
+
+impl<const D: usize> PointSet<D> {
+    pub fn new() -> Self {
+        Self {
+            points: HashSet::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn contains(&self, point: PointN<D>) -> bool {
+        self.points.contains(&point)
+    }
+
+    /// Returns whether the point was newly added.
+    pub fn insert(&mut self, point: PointN<D>) -> bool {
+        self.points.insert(point)
+    }
+
+    /// Returns whether the point was in the set.
+    pub fn remove(&mut self, point: PointN<D>) -> bool {
+        self.points.remove(&point)
+    }
+
+    /// All points, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = PointN<D>> + '_ {
+        self.points.iter().copied()
+    }
+
+    pub fn bounds(&self) -> Option<BoundingBox<D>> {
+        BoundingBox::from_points(self.iter())
+    }
+
+    /// Number of von Neumann neighbours of `point` that are in the set.
+    pub fn count_von_neumann_neighbours(&self, point: PointN<D>) -> usize {
+        point
+            .von_neumann_neighbours()
+            .filter(|&n| self.contains(n))
+            .count()
+    }
+
+    /// Number of Moore neighbours of `point` that are in the set.
+    pub fn count_moore_neighbours(&self, point: PointN<D>) -> usize {
+        point
+            .moore_neighbours()
+            .filter(|&n| self.contains(n))
+            .count()
+    }
+
+    /// Number of unit cell faces that aren't shared by two points of the set.
+    pub fn surface_area(&self) -> usize {
+        self.iter()
+            .map(|p| 2 * D - self.count_von_neumann_neighbours(p))
+            .sum()
+    }
+
+    /// Like [`PointSet::surface_area`], but only counts faces that can be reached from outside,
+    /// ignoring any air pockets enclosed by the set.
+    pub fn exterior_surface_area(&self) -> usize {
+        let Some(bounds) = self.bounds().map(|b| b.expand(1)) else {
+            return 0;
+        };
+        let mut visited = HashSet::from([bounds.min()]);
+        let mut queue = VecDeque::from([bounds.min()]);
+        let mut faces = 0;
+        while let Some(point) = queue.pop_front() {
+            for n in point.von_neumann_neighbours() {
+                if self.contains(n) {
+                    faces += 1;
+                } else if bounds.contains(n) && visited.insert(n) {
+                    queue.push_back(n);
+                }
+            }
+        }
+        faces
+    }
+}
+
+impl<const D: usize> Hash for PointSet<D> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut points = self.iter().collect::<Vec<_>>();
+        points.sort_unstable();
+        points.hash(state);
+    }
+}
+
+impl<const D: usize> FromIterator<PointN<D>> for PointSet<D> {
+    fn from_iter<I: IntoIterator<Item = PointN<D>>>(iter: I) -> Self {
+        Self {
+            points: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<const D: usize> Extend<PointN<D>> for PointSet<D> {
+    fn extend<I: IntoIterator<Item = PointN<D>>>(&mut self, iter: I) {
+        self.points.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{BoundingBox, Point2, Point3, PointN, PointSet};
+
+    const DROPLET: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    #[test]
+    fn arithmetic() {
+        let a = Point3::new([1, -2, 3]);
+        let b = Point3::new([4, 5, -6]);
+        assert_eq!(a + b, Point3::new([5, 3, -3]));
+        assert_eq!(a - b, Point3::new([-3, -7, 9]));
+        assert_eq!(-a, Point3::new([-1, 2, -3]));
+        assert_eq!(a * 2, Point3::new([2, -4, 6]));
+        assert_eq!(a[1], -2);
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point3::new([1, -2, 3]);
+        let b = Point3::new([4, 2, 3]);
+        assert_eq!(a.manhattan_distance(&b), 7);
+        assert_eq!(a.squared_distance(&b), 25);
+        assert_eq!(b.squared_distance(&a), 25);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("1,-2, 3".parse::<Point3>(), Ok(Point3::new([1, -2, 3])));
+        assert!("1,2".parse::<Point3>().is_err());
+        assert!("1,2,3,4".parse::<Point3>().is_err());
+        assert!("1,x,3".parse::<Point3>().is_err());
+    }
+
+    #[test]
+    fn neighbours() {
+        let p = PointN::new([1, 1, 1, 1]);
+        assert_eq!(p.von_neumann_neighbours().count(), 8);
+        assert_eq!(p.moore_neighbours().count(), 80);
+        let moore = p.moore_neighbours().collect::<HashSet<_>>();
+        assert_eq!(moore.len(), 80);
+        assert!(!moore.contains(&p));
+        assert!(moore
+            .iter()
+            .all(|n| (0..4).all(|i| n[i].abs_diff(p[i]) <= 1)));
+        assert!(p
+            .von_neumann_neighbours()
+            .all(|n| n.manhattan_distance(&p) == 1));
+    }
+
+    #[test]
+    fn bounding_box() {
+        let bounds = BoundingBox::from_points([Point2::new([1, 5]), Point2::new([-2, 3])]).unwrap();
+        assert_eq!(bounds.min(), Point2::new([-2, 3]));
+        assert_eq!(bounds.max(), Point2::new([1, 5]));
+        assert_eq!(bounds.volume(), 12);
+        assert_eq!(bounds.iter().count(), 12);
+        assert!(bounds.iter().all(|p| bounds.contains(p)));
+        assert!(!bounds.contains(Point2::new([2, 5])));
+        assert_eq!(bounds.expand(1).volume(), 30);
+        assert!(BoundingBox::<2>::from_points([]).is_none());
+    }
+
+    #[test]
+    fn surface_area() {
+        let pair = [Point3::new([1, 1, 1]), Point3::new([2, 1, 1])]
+            .into_iter()
+            .collect::<PointSet<3>>();
+        assert_eq!(pair.surface_area(), 10);
+
+        let droplet = DROPLET
+            .lines()
+            .map(|l| l.parse().unwrap())
+            .collect::<PointSet<3>>();
+        assert_eq!(droplet.surface_area(), 64);
+        assert_eq!(droplet.exterior_surface_area(), 58);
+        assert_eq!(PointSet::<3>::new().exterior_surface_area(), 0);
+    }
+}
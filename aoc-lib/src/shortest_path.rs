@@ -0,0 +1,381 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Cheapest path from any of `starts` to a state accepted by `is_goal`. `successors` returns
+/// the states reachable from a state together with the (non-negative) cost of getting there.
+pub fn dijkstra<S, C, I, FN, IN, FG>(starts: I, successors: FN, is_goal: FG) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`] but keeps track of every optimal path, not just one of them.
+pub fn dijkstra_all<S, C, I, FN, IN, FG>(
+    starts: I,
+    successors: FN,
+    is_goal: FG,
+) -> Option<OptimalPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    astar_all(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Dijkstra guided by `heuristic`, which must never overestimate the remaining cost to a goal.
+pub fn astar<S, C, I, FN, IN, FH, FG>(
+    starts: I,
+    successors: FN,
+    heuristic: FH,
+    is_goal: FG,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    search(starts, successors, heuristic, is_goal, false).map(|paths| (paths.path(), paths.cost))
+}
+
+/// Like [`astar`] but keeps track of every optimal path, not just one of them.
+pub fn astar_all<S, C, I, FN, IN, FH, FG>(
+    starts: I,
+    successors: FN,
+    heuristic: FH,
+    is_goal: FG,
+) -> Option<OptimalPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    search(starts, successors, heuristic, is_goal, true)
+}
+
+/// The cost of the cheapest path from any of `starts` to every state that can be reached. Unlike
+/// [`dijkstra`] this doesn't stop at a goal, so there must only be finitely many states. For a
+/// graph from [`crate::graph`] see [`crate::graph::algorithms::distances_from`].
+pub fn distances<S, C, I, FN, IN>(starts: I, successors: FN) -> HashMap<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+{
+    let explored = explore(starts, successors, |_| C::default(), |_| false, false);
+    explored.states.into_iter().zip(explored.costs).collect()
+}
+
+/// The predecessor graph of all optimal paths found by [`dijkstra_all`] or [`astar_all`].
+pub struct OptimalPaths<S, C> {
+    cost: C,
+    states: Vec<S>,
+    index: HashMap<S, usize>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+impl<S, C> OptimalPaths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy,
+{
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+
+    /// All goal states that can be reached at the optimal cost.
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|&i| &self.states[i])
+    }
+
+    /// The states directly before `state` on some optimal path.
+    pub fn predecessors(&self, state: &S) -> impl Iterator<Item = &S> {
+        self.index
+            .get(state)
+            .map(|&i| self.predecessors[i].as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|&i| &self.states[i])
+    }
+
+    /// One of the optimal paths, from start to goal.
+    pub fn path(&self) -> Vec<S> {
+        let mut path = Vec::new();
+        let mut current = self.goals[0];
+        loop {
+            path.push(self.states[current].clone());
+            match self.predecessors[current].first() {
+                Some(&prev) => current = prev,
+                None => break,
+            }
+        }
+        path.reverse();
+        path
+    }
+
+    /// Every state that is part of at least one optimal path.
+    pub fn states(&self) -> impl Iterator<Item = &S> {
+        let mut visited = HashSet::new();
+        let mut stack = self.goals.clone();
+        while let Some(i) = stack.pop() {
+            if visited.insert(i) {
+                stack.extend(self.predecessors[i].iter().copied());
+            }
+        }
+        visited.into_iter().map(|i| &self.states[i])
+    }
+}
+
+fn search<S, C, I, FN, IN, FH, FG>(
+    starts: I,
+    successors: FN,
+    heuristic: FH,
+    is_goal: FG,
+    all_paths: bool,
+) -> Option<OptimalPaths<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    let explored = explore(starts, successors, heuristic, is_goal, all_paths);
+    explored.best.map(|cost| OptimalPaths {
+        cost,
+        states: explored.states,
+        index: explored.index,
+        predecessors: explored.predecessors,
+        goals: explored.goals,
+    })
+}
+
+/// Everything [`explore`] found. When no goal is reached every reachable state has been
+/// explored, and `costs` holds the cheapest cost of each.
+struct Explored<S, C> {
+    best: Option<C>,
+    states: Vec<S>,
+    index: HashMap<S, usize>,
+    costs: Vec<C>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+fn explore<S, C, I, FN, IN, FH, FG>(
+    starts: I,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+    all_paths: bool,
+) -> Explored<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = S>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    let mut states: Vec<S> = Vec::new();
+    let mut index: HashMap<S, usize> = HashMap::new();
+    let mut costs: Vec<C> = Vec::new();
+    let mut predecessors: Vec<Vec<usize>> = Vec::new();
+    let mut done: Vec<bool> = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if index.contains_key(&start) {
+            continue;
+        }
+        let i = states.len();
+        heap.push(Reverse((heuristic(&start), C::default(), i)));
+        index.insert(start.clone(), i);
+        states.push(start);
+        costs.push(C::default());
+        predecessors.push(Vec::new());
+        done.push(false);
+    }
+
+    let mut best = None;
+    let mut goals = Vec::new();
+
+    while let Some(Reverse((estimate, cost, i))) = heap.pop() {
+        if done[i] || cost > costs[i] {
+            continue;
+        }
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+        done[i] = true;
+
+        if is_goal(&states[i]) {
+            best = Some(cost);
+            goals.push(i);
+            if all_paths {
+                continue;
+            }
+            break;
+        }
+
+        for (next, step_cost) in successors(&states[i]) {
+            let next_cost = cost + step_cost;
+            match index.get(&next) {
+                Some(&n) => match next_cost.cmp(&costs[n]) {
+                    Ordering::Less => {
+                        costs[n] = next_cost;
+                        predecessors[n] = vec![i];
+                        heap.push(Reverse((next_cost + heuristic(&next), next_cost, n)));
+                    }
+                    Ordering::Equal if all_paths && !predecessors[n].contains(&i) => {
+                        predecessors[n].push(i);
+                    }
+                    _ => {}
+                },
+                None => {
+                    let n = states.len();
+                    heap.push(Reverse((next_cost + heuristic(&next), next_cost, n)));
+                    index.insert(next.clone(), n);
+                    states.push(next);
+                    costs.push(next_cost);
+                    predecessors.push(vec![i]);
+                    done.push(false);
+                }
+            }
+        }
+    }
+
+    Explored {
+        best,
+        states,
+        index,
+        costs,
+        predecessors,
+        goals,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::grid::{Grid, Pos};
+
+    const MAZE: &str = "S..#
+.#..
+...E";
+
+    fn moves<'a>(grid: &'a Grid<char>) -> impl FnMut(&Pos) -> Vec<(Pos, usize)> + 'a {
+        |&pos| {
+            grid.neighbours4(pos)
+                .filter(|n| *n.value != '#')
+                .map(|n| (n.pos, 1))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn dijkstra() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+        let (path, cost) =
+            super::dijkstra([Pos::new(0, 0)], moves(&grid), |&p| p == Pos::new(2, 3)).unwrap();
+
+        assert_eq!(cost, 5);
+        assert_eq!(path.len(), 6);
+        assert_eq!(path[0], Pos::new(0, 0));
+        assert_eq!(path[5], Pos::new(2, 3));
+        assert!(path.windows(2).all(|w| w[0].distance_to(&w[1]) == 1));
+    }
+
+    #[test]
+    fn dijkstra_unreachable() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+        assert!(
+            super::dijkstra([Pos::new(0, 0)], moves(&grid), |&p| p == Pos::new(0, 3)).is_none()
+        );
+    }
+
+    #[test]
+    fn dijkstra_weighted() {
+        let grid = "1163\n1381\n2136".parse::<Grid<u32>>().unwrap();
+        let (_, cost) = super::dijkstra(
+            [Pos::new(0, 0)],
+            |&pos| {
+                grid.neighbours4(pos)
+                    .map(|n| (n.pos, *n.value))
+                    .collect::<Vec<_>>()
+            },
+            |&p| p == Pos::new(2, 3),
+        )
+        .unwrap();
+        assert_eq!(cost, 13);
+    }
+
+    #[test]
+    fn distances() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+        let distances = super::distances([Pos::new(0, 0)], moves(&grid));
+        assert_eq!(distances.len(), 10);
+        assert_eq!(distances[&Pos::new(2, 3)], 5);
+        assert_eq!(distances[&Pos::new(0, 2)], 2);
+        assert!(!distances.contains_key(&Pos::new(1, 1)));
+    }
+
+    #[test]
+    fn astar() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+        let goal = Pos::new(2, 3);
+        let (path, cost) = super::astar(
+            [Pos::new(0, 0)],
+            moves(&grid),
+            |p| p.distance_to(&goal),
+            |&p| p == goal,
+        )
+        .unwrap();
+
+        assert_eq!(cost, 5);
+        assert_eq!(path.last(), Some(&goal));
+    }
+
+    #[test]
+    fn all_optimal_paths() {
+        let grid = MAZE.parse::<Grid<char>>().unwrap();
+        let paths =
+            super::dijkstra_all([Pos::new(0, 0)], moves(&grid), |&p| p == Pos::new(2, 3)).unwrap();
+
+        assert_eq!(paths.cost(), 5);
+        assert_eq!(paths.goals().collect::<Vec<_>>(), [&Pos::new(2, 3)]);
+        let on_path = paths.states().copied().collect::<HashSet<_>>();
+        assert_eq!(on_path.len(), 10);
+        assert!(!on_path.contains(&Pos::new(0, 3)));
+        assert!(!on_path.contains(&Pos::new(1, 1)));
+        assert_eq!(paths.predecessors(&Pos::new(2, 3)).count(), 2);
+        assert_eq!(paths.predecessors(&Pos::new(0, 0)).count(), 0);
+    }
+}
@@ -1,4 +1,7 @@
-use std::collections::HashSet;
+use aoc_lib::graph::{
+    weighted::{Weighted, WeightedGraph},
+    DirectedGraph,
+};
 
 pub fn solve_part_1() -> u32 {
     let map = Map::new(input().as_slice());
@@ -13,25 +16,18 @@ pub fn solve_part_2() -> u32 {
 type Location = String;
 
 struct Map {
-    edges: Vec<(Location, Location, u32)>,
+    graph: WeightedGraph<Location, u32>,
 }
 
 impl Map {
     fn new(paths: &[(Location, Location, u32)]) -> Map {
-        let mut edges = Vec::new();
-        for (l1, l2, distance) in paths.iter() {
-            edges.push((l2.clone(), l1.clone(), *distance));
-            edges.push((l1.clone(), l2.clone(), *distance));
+        Map {
+            graph: WeightedGraph::undirected(paths.iter().cloned()),
         }
-        Map { edges }
     }
 
     fn solve_part_1(&self) -> u32 {
-        let all_locations = self
-            .edges
-            .iter()
-            .map(|(l1, _, _)| l1)
-            .collect::<HashSet<_>>();
+        let all_locations = self.graph.nodes().collect::<Vec<_>>();
 
         return all_locations
             .iter()
@@ -51,11 +47,7 @@ impl Map {
     }
 
     fn solve_part_2(&self) -> u32 {
-        let all_locations = self
-            .edges
-            .iter()
-            .map(|(l1, _, _)| l1)
-            .collect::<HashSet<_>>();
+        let all_locations = self.graph.nodes().collect::<Vec<_>>();
 
         return all_locations
             .iter()
@@ -80,11 +72,7 @@ impl Map {
         }
         let mut shortest_distance = u32::MAX;
         for &next in to.iter() {
-            let (_, _, dist) = self
-                .edges
-                .iter()
-                .find(|(l1, l2, _)| l1 == from && l2 == next)
-                .expect("Can't find edge");
+            let dist = self.graph.weight(from, next).expect("Can't find edge");
 
             let distance_this_path = self.find_shortest_path(
                 next,
@@ -102,11 +90,7 @@ impl Map {
         }
         let mut longest_distance = u32::MIN;
         for &next in to.iter() {
-            let (_, _, dist) = self
-                .edges
-                .iter()
-                .find(|(l1, l2, _)| l1 == from && l2 == next)
-                .expect("Can't find edge");
+            let dist = self.graph.weight(from, next).expect("Can't find edge");
 
             let distance_this_path = self.find_longest_path(
                 next,
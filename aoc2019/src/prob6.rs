@@ -1,3 +1,5 @@
+use aoc_lib::graph::{algorithms::hop_counts, AdjacencyListGraph};
+
 pub fn solve_part_1() {
     let count = solve_part_1_for(input());
     println!("Part 1: {}", count);
@@ -21,25 +23,13 @@ fn count_orbits(graph: &Vec<(String, String)>, node: &str, count: u32) -> u32 {
 }
 
 fn bfs(graph: &Vec<(String, String)>, from: &String, to: &String) -> u32 {
-    let mut queue: Vec<(&String, u32)> = vec![(from, 0)];
-    let mut visited: Vec<&String> = Vec::new();
-    while !queue.is_empty() {
-        let (node, count) = queue.remove(0);
-        if node == to {
-            return count - 2;
-        }
-        let mut neighbours: Vec<(&String, u32)> = graph
+    let orbits = AdjacencyListGraph::new(
+        graph
             .iter()
-            .filter(|(c, o)| c == node || o == node)
-            .map(|(c, o)| if c == node { o } else { c })
-            .filter(|n| !visited.contains(n))
-            .map(|n| (n, count + 1))
-            .collect();
-        //println!("Visiting {}, {:?}", node, &neighbours);
-        visited.push(node);
-        queue.append(&mut neighbours);
-    }
-    panic!("Not found");
+            .flat_map(|(c, o)| [(c.clone(), o.clone()), (o.clone(), c.clone())]),
+    );
+    let hops = hop_counts(&orbits, from);
+    *hops.get(to).expect("Not found") as u32 - 2
 }
 
 #[cfg(test)]
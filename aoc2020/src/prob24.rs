@@ -1,39 +1,19 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+
+use aoc_lib::{
+    automaton::{Automaton, Neighbourhood},
+    hex::{Hex, HexDirection, HexMap},
+};
 
 pub fn solve_part_1(input: &str) -> usize {
     initial_state(input).len()
 }
 
-fn initial_state(input: &str) -> HashSet<(i32, i32)> {
+fn initial_state(input: &str) -> HashSet<Hex> {
     let mut black_tiles = HashSet::new();
     for line in input.lines() {
-        let mut chars = line.chars();
-        let mut position: (i32, i32) = (0, 0);
-        while let Some(c) = chars.next() {
-            match c {
-                'e' => position.0 += 1,
-                'w' => position.0 -= 1,
-                'n' => {
-                    let c2 = chars.next().expect("Malformed input");
-                    if c2 == 'e' && position.1.abs() % 2 == 0 {
-                        position.0 += 1;
-                    } else if c2 == 'w' && position.1.abs() % 2 == 1 {
-                        position.0 -= 1;
-                    }
-                    position.1 += 1;
-                }
-                's' => {
-                    let c2 = chars.next().expect("Malformed input");
-                    if c2 == 'e' && position.1.abs() % 2 == 0 {
-                        position.0 += 1;
-                    } else if c2 == 'w' && position.1.abs() % 2 == 1 {
-                        position.0 -= 1;
-                    }
-                    position.1 -= 1;
-                }
-                _ => panic!("Malformed input"),
-            }
-        }
+        let path = HexDirection::parse_path(line).expect("Malformed input");
+        let position = Hex::ORIGIN.walk(&path);
         if black_tiles.contains(&position) {
             black_tiles.remove(&position);
         } else {
@@ -44,65 +24,17 @@ fn initial_state(input: &str) -> HashSet<(i32, i32)> {
 }
 
 pub fn solve_part_2(input: &str) -> usize {
-    let mut state = initial_state(input);
-
-    for _ in 0..100 {
-        let mut changes: HashMap<(i32, i32), u32> = HashMap::new();
-        for p in state.iter() {
-            for n in p.neighbours() {
-                if let Some(p2) = changes.get_mut(&n) {
-                    *p2 += 1;
-                } else {
-                    changes.insert(n, 1);
-                }
-            }
-        }
-        state = changes
-            .into_iter()
-            .filter(|(p, c)| {
-                if state.contains(p) {
-                    *c == 1 || *c == 2
-                } else {
-                    *c == 2
-                }
-            })
-            .map(|(p, _)| p)
-            .collect();
+    let mut tiles = HexMap::new(false);
+    for hex in initial_state(input) {
+        tiles.insert(hex, true);
     }
-    state.len()
-}
-
-trait Node {
-    fn neighbours(&self) -> Vec<Self> where Self: Sized;
-}
 
-impl Node for (i32, i32) {
-    fn neighbours(&self) -> Vec<Self> {
-        vec![
-            (self.0 + 1, self.1),
-            (self.0 - 1, self.1),
-            if self.1.abs() % 2 == 0 {
-                (self.0 + 1, self.1 + 1)
-            } else {
-                (self.0, self.1 + 1)
-            },
-            if self.1.abs() % 2 == 1 {
-                (self.0 - 1, self.1 + 1)
-            } else {
-                (self.0, self.1 + 1)
-            },
-            if self.1.abs() % 2 == 0 {
-                (self.0 + 1, self.1 - 1)
-            } else {
-                (self.0, self.1 - 1)
-            },
-            if self.1.abs() % 2 == 1 {
-                (self.0 - 1, self.1 - 1)
-            } else {
-                (self.0, self.1 - 1)
-            },
-        ]
-    }
+    let mut automaton = Automaton::new(tiles, Neighbourhood::Moore, |_, &black, n: &[bool]| {
+        let count = n.iter().filter(|&&n| n).count();
+        count == 2 || (black && count == 1)
+    });
+    automaton.run(100);
+    automaton.space().len()
 }
 
 #[cfg(test)]
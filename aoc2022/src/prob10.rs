@@ -1,3 +1,5 @@
+use aoc_lib::{grid::Grid, ocr};
+
 pub fn solve_part_1(input: &str) -> usize {
     let mut signal_strength = 0;
     let mut cycle = 0;
@@ -46,7 +48,15 @@ impl CRT {
     }
 }
 
-pub fn solve_part_2(input: &str) -> usize {
+pub fn solve_part_2(input: &str) -> String {
+    let screen = draw(input)
+        .parse::<Grid<char>>()
+        .expect("Invalid screen")
+        .map(|&c| c == '#');
+    ocr::recognize(&screen).expect("Screen doesn't show letters")
+}
+
+fn draw(input: &str) -> String {
     let mut crt = CRT::default();
     let mut x_register = 1;
     for instr in input.lines() {
@@ -59,13 +69,12 @@ pub fn solve_part_2(input: &str) -> usize {
         }
     }
 
-    println!("{}", crt.data);
-    0
+    crt.data
 }
 
 #[cfg(test)]
 mod test {
-    use crate::prob10::{solve_part_1, solve_part_2};
+    use crate::prob10::{draw, solve_part_1, solve_part_2};
 
     #[test]
     fn test() {
@@ -74,7 +83,11 @@ mod test {
 
     #[test]
     fn test2() {
-        assert_eq!(solve_part_2(INPUT), 0);
+        assert_eq!(solve_part_2(include_str!("../inputs/prob10")), "ZUPRFECL");
+        assert_eq!(
+            draw(INPUT).lines().next(),
+            Some("##..##..##..##..##..##..##..##..##..##..")
+        );
     }
 
     const INPUT: &'static str = r"addx 15
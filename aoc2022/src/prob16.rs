@@ -1,5 +1,7 @@
 use std::cmp::max;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, VecDeque};
+
+use aoc_lib::graph::{algorithms::hop_counts, AdjacencyListGraph};
 
 #[derive(Clone)]
 struct Valve {
@@ -35,34 +37,6 @@ impl Valve {
             tunnels,
         }
     }
-
-    fn steps_to(&self, destination: &str, all_valves: &Vec<Valve>) -> u32 {
-        let mut visited = HashSet::new();
-        let mut queue = VecDeque::new();
-        queue.push_back((&self.id, 0));
-
-        while let Some((next, steps)) = queue.pop_front() {
-            if next == destination {
-                return steps;
-            }
-
-            visited.insert(next);
-            let valve = all_valves
-                .iter()
-                .find(|v| &v.id == next)
-                .expect(&format!("Doesn't exist {}", next));
-
-            for tunnel in valve.tunnels.iter() {
-                if visited.contains(tunnel) {
-                    continue;
-                }
-
-                queue.push_back((tunnel, steps + 1));
-            }
-        }
-
-        panic!("Not found");
-    }
 }
 
 fn dynamic_programming(valves: &Vec<&Valve>, connections: &Vec<Vec<u32>>) -> Vec<Vec<Vec<u32>>> {
@@ -111,36 +85,40 @@ fn dynamic_programming(valves: &Vec<&Valve>, connections: &Vec<Vec<u32>>) -> Vec
     state
 }
 
-fn create_state_map(valves: &Vec<Valve>) -> Vec<Vec<u32>> {
-    let valves_with_rate = valves.iter().filter(|v| v.rate > 0).collect::<Vec<_>>();
-
-    let mut state = vec![vec![0; valves_with_rate.len()]; valves_with_rate.len()];
-    for (v1_index, v1) in valves_with_rate.iter().enumerate() {
-        for (v2_index, v2) in valves_with_rate.iter().enumerate() {
-            if v1_index == v2_index {
-                continue;
-            }
-            let steps = v1.steps_to(v2.id.as_str(), valves);
-
-            state[v1_index][v2_index] = steps;
-        }
-    }
+fn tunnels(valves: &[Valve]) -> AdjacencyListGraph<String> {
+    AdjacencyListGraph::new(valves.iter().flat_map(|v| {
+        v.tunnels
+            .iter()
+            .map(move |tunnel| (v.id.clone(), tunnel.clone()))
+    }))
+}
 
-    state
+fn create_state_map(valves: &[&Valve], tunnels: &AdjacencyListGraph<String>) -> Vec<Vec<u32>> {
+    valves
+        .iter()
+        .map(|v1| {
+            let steps = hop_counts(tunnels, &v1.id);
+            valves
+                .iter()
+                .map(|v2| *steps.get(&v2.id).expect("Not found") as u32)
+                .collect()
+        })
+        .collect()
 }
 
 pub fn solve_part_1(input: &str) -> usize {
     let valves: Vec<_> = input.lines().map(|line| Valve::parse(line)).collect();
 
-    let start_valve = valves.iter().find(|v| v.id == "AA").unwrap();
+    let tunnels = tunnels(&valves);
+    let from_start = hop_counts(&tunnels, &"AA".to_string());
     let valves_with_rate = valves.iter().filter(|v| v.rate > 0).collect::<Vec<_>>();
-    let state = create_state_map(&valves);
+    let state = create_state_map(&valves_with_rate, &tunnels);
 
     let result = dynamic_programming(&valves_with_rate, &state);
 
     let mut max_pressure = 0;
     for (i, valve) in valves_with_rate.iter().enumerate() {
-        let steps = start_valve.steps_to(valve.id.as_str(), &valves) + 1;
+        let steps = from_start[&valve.id] as u32 + 1;
 
         max_pressure = max(
             max_pressure,
@@ -156,14 +134,15 @@ pub fn solve_part_1(input: &str) -> usize {
 pub fn solve_part_2(input: &str) -> usize {
     let valves: Vec<_> = input.lines().map(|line| Valve::parse(line)).collect();
 
-    let start_valve = valves.iter().find(|v| v.id == "AA").unwrap();
+    let tunnels = tunnels(&valves);
+    let from_start = hop_counts(&tunnels, &"AA".to_string());
     let valves_with_rate = valves.iter().filter(|v| v.rate > 0).collect::<Vec<_>>();
-    let state = create_state_map(&valves);
+    let state = create_state_map(&valves_with_rate, &tunnels);
     let mut best = HashMap::new();
 
     let mut queue = VecDeque::new();
     for (index, valve) in valves_with_rate.iter().enumerate() {
-        let steps = start_valve.steps_to(valve.id.as_str(), &valves);
+        let steps = from_start[&valve.id] as u32;
         let minutes_left = 30 - (steps + 1);
         queue.push_back((index, minutes_left, minutes_left * valve.rate, 0b1 << index));
         best.insert(0b1 << index, minutes_left * valve.rate);
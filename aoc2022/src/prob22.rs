@@ -1,6 +1,7 @@
 use crate::prob22::Direction::{Down, Left, Right, Up};
 use crate::prob22::Instruction::{TurnLeft, TurnRight, Walk};
 use crate::prob22::Tile::{Empty, Open, Wall};
+use aoc_lib::grid::{cube::CubeNet, Pos};
 use itertools::Itertools;
 use nom::branch::alt;
 use nom::character::complete;
@@ -40,6 +41,25 @@ impl Direction {
         }
     }
 
+    fn to_grid(&self) -> aoc_lib::grid::Direction {
+        match self {
+            Up => aoc_lib::grid::Direction::Up,
+            Right => aoc_lib::grid::Direction::Right,
+            Down => aoc_lib::grid::Direction::Down,
+            Left => aoc_lib::grid::Direction::Left,
+        }
+    }
+
+    fn from_grid(direction: aoc_lib::grid::Direction) -> Direction {
+        match direction {
+            aoc_lib::grid::Direction::Up => Up,
+            aoc_lib::grid::Direction::Right => Right,
+            aoc_lib::grid::Direction::Down => Down,
+            aoc_lib::grid::Direction::Left => Left,
+            _ => unreachable!(),
+        }
+    }
+
     fn score(&self) -> usize {
         match self {
             Up => 3,
@@ -66,11 +86,11 @@ enum Tile {
 
 struct Map {
     data: Vec<Vec<Tile>>,
-    wrap_around: Option<Vec<(Bounds, Box<dyn WrapAround>)>>,
+    cube: Option<CubeNet>,
 }
 
 impl Map {
-    fn parse(s: &str, wrap_around: Option<Vec<(Bounds, Box<dyn WrapAround>)>>) -> Self {
+    fn parse(s: &str, fold_into_cube: bool) -> Self {
         let data = s
             .lines()
             .map(|line| {
@@ -87,7 +107,16 @@ impl Map {
                     .collect::<Vec<_>>()
             })
             .collect::<Vec<_>>();
-        Map { data, wrap_around }
+        let cube = fold_into_cube.then(|| {
+            let cells = data.iter().enumerate().flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, tile)| **tile != Empty)
+                    .map(move |(x, _)| Pos::new(y, x))
+            });
+            CubeNet::new(cells).expect("Map is not a cube net")
+        });
+        Map { data, cube }
     }
 
     fn top_left(&self) -> Point {
@@ -236,28 +265,30 @@ impl Map {
     }
 
     fn wrap_around(&self, point: &Point, direction: &Direction) -> Option<(Point, Direction)> {
-        self.wrap_around.as_ref().map(|wrap_around_rules| {
-            let (_, wrap_rule) = wrap_around_rules
-                .iter()
-                .find(|(bounds, _)| bounds.contains(point))
-                .expect("No wrap rule");
-            let next = wrap_rule.next(point, direction);
-            next
+        self.cube.as_ref().map(|cube| {
+            let (pos, dir) = cube.step(Pos::new(point.y, point.x), direction.to_grid());
+            (
+                Point {
+                    x: pos.col(),
+                    y: pos.row(),
+                },
+                Direction::from_grid(dir),
+            )
         })
     }
 }
 
 pub fn solve_part_1(input: &str) -> usize {
-    solve(input, None)
+    solve(input, false)
 }
 
 pub fn solve_part_2(input: &str) -> usize {
-    solve(input, Some(wrap_around()))
+    solve(input, true)
 }
 
-fn solve(input: &str, wrap_around: Option<Vec<(Bounds, Box<dyn WrapAround>)>>) -> usize {
+fn solve(input: &str, fold_into_cube: bool) -> usize {
     let mut split = input.split("\n\n");
-    let map_data = Map::parse(split.next().unwrap(), wrap_around);
+    let map_data = Map::parse(split.next().unwrap(), fold_into_cube);
 
     let mut direction = Right;
     let mut point = map_data.top_left();
@@ -288,203 +319,9 @@ fn solve(input: &str, wrap_around: Option<Vec<(Bounds, Box<dyn WrapAround>)>>) -
     1000 * (point.y + 1) + 4 * (point.x + 1) + direction.score()
 }
 
-struct Bounds((Point, Point));
-
-impl Bounds {
-    fn contains(&self, point: &Point) -> bool {
-        (self.0 .0.x..self.0 .1.x).contains(&point.x)
-            && (self.0 .0.y..self.0 .1.y).contains(&point.y)
-    }
-}
-
-trait WrapAround {
-    fn next(&self, point: &Point, direction: &Direction) -> (Point, Direction);
-}
-
-fn wrap_around() -> Vec<(Bounds, Box<dyn WrapAround>)> {
-    vec![
-        (
-            Bounds((Point { x: 50, y: 0 }, Point { x: 100, y: 50 })),
-            Box::new(Square1),
-        ),
-        (
-            Bounds((Point { x: 100, y: 0 }, Point { x: 150, y: 50 })),
-            Box::new(Square2),
-        ),
-        (
-            Bounds((Point { x: 50, y: 50 }, Point { x: 100, y: 100 })),
-            Box::new(Square3),
-        ),
-        (
-            Bounds((Point { x: 0, y: 100 }, Point { x: 50, y: 150 })),
-            Box::new(Square4),
-        ),
-        (
-            Bounds((Point { x: 50, y: 100 }, Point { x: 50+50, y: 100+50 })),
-            Box::new(Square5),
-        ),
-        (
-            Bounds((Point { x: 0, y: 150 }, Point { x: 0+50, y: 150+50 })),
-            Box::new(Square6),
-        ),
-    ]
-}
-
-struct Square1;
-impl WrapAround for Square1 {
-    fn next(&self, point: &Point, direction: &Direction) -> (Point, Direction) {
-        match direction {
-            Up => (
-                Point {
-                    x: 0,
-                    y: 150 + (point.x-50),
-                },
-                Right,
-            ),
-            Right => unreachable!(),
-            Down => unreachable!(),
-            Left => (
-                Point {
-                    x: 0,
-                    y: 149 - point.y,
-                },
-                Right,
-            ),
-        }
-    }
-}
-struct Square2;
-impl WrapAround for Square2 {
-    fn next(&self, point: &Point, direction: &Direction) -> (Point, Direction) {
-        match direction {
-            Up => (
-                Point {
-                    x: point.x - 100,
-                    y: 199,
-                },
-                Up,
-            ),
-            Right => (
-                Point {
-                    x: 99,
-                    y: 149 - point.y,
-                },
-                Left,
-            ),
-            Down => (
-                Point {
-                    x: 99,
-                    y: (point.x - 100) + 50,
-                },
-                Left,
-            ),
-            Left => unreachable!(),
-        }
-    }
-}
-struct Square3;
-impl WrapAround for Square3 {
-    fn next(&self, point: &Point, direction: &Direction) -> (Point, Direction) {
-        match direction {
-            Up => unreachable!(),
-            Right => (
-                Point {
-                    x: (point.y - 50) + 100,
-                    y: 49,
-                },
-                Up,
-            ),
-            Down => unreachable!(),
-            Left => (
-                Point {
-                    x: (point.y - 50),
-                    y: 100,
-                },
-                Down,
-            ),
-        }
-    }
-}
-struct Square4;
-impl WrapAround for Square4 {
-    fn next(&self, point: &Point, direction: &Direction) -> (Point, Direction) {
-        match direction {
-            Up => (
-                Point {
-                    x: 50,
-                    y: point.x + 50,
-                },
-                Right,
-            ),
-            Right => unreachable!(),
-            Down => unreachable!(),
-            Left => (
-                Point {
-                    x: 50,
-                    y: 49 - (point.y - 100),
-                },
-                Right,
-            ),
-        }
-    }
-}
-struct Square5;
-impl WrapAround for Square5 {
-    fn next(&self, point: &Point, direction: &Direction) -> (Point, Direction) {
-        match direction {
-            Up => unreachable!(),
-            Right => (
-                Point {
-                    x: 149,
-                    y: 49 - (point.y - 100),
-                },
-                Left,
-            ),
-            Down => (
-                Point {
-                    x: 49,
-                    y: (point.x - 50) + 150,
-                },
-                Left,
-            ),
-            Left => unreachable!(),
-        }
-    }
-}
-struct Square6;
-impl WrapAround for Square6 {
-    fn next(&self, point: &Point, direction: &Direction) -> (Point, Direction) {
-        match direction {
-            Up => unreachable!(),
-            Right => (
-                Point {
-                    x: (point.y - 150) + 50,
-                    y: 149,
-                },
-                Up,
-            ),
-            Down => (
-                Point {
-                    x: point.x + 100,
-                    y: 0,
-                },
-                Down,
-            ),
-            Left => (
-                Point {
-                    x: (point.y - 150) + 50,
-                    y: 0,
-                },
-                Down,
-            ),
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
-    use crate::prob22::Direction::{Down, Left, Right, Up};
-    use crate::prob22::{solve, solve_part_1, Bounds, Direction, Point, WrapAround, solve_part_2};
+    use crate::prob22::{solve_part_1, solve_part_2};
 
     #[test]
     fn test_1() {
@@ -502,41 +339,18 @@ mod test {
 
     #[test]
     fn test_2_2() {
-        assert_eq!(
-            solve(INPUT_2, Some(wrap_around_test())),
-            1000 * 8 + 4 * 5 + 1
-        );
+        assert_eq!(solve_part_2(INPUT_2), 1000 * 8 + 4 * 5 + 1);
     }
 
     #[test]
-    fn test_2() {
-        assert_eq!(solve_part_2(include_str!("../inputs/prob22")), 123046);
+    fn test_2_example() {
+        let input = INPUT_2.replace("LL4", "10R5L5R10L4R5L5");
+        assert_eq!(solve_part_2(&input), 5031);
     }
 
-    fn wrap_around_test() -> Vec<(Bounds, Box<dyn WrapAround>)> {
-        vec![(
-            Bounds((Point { x: 8, y: 0 }, Point { x: 8 + 4, y: 0 + 3 })),
-            Box::new(Square1),
-        )]
-    }
-
-    struct Square1;
-
-    impl WrapAround for Square1 {
-        fn next(&self, point: &Point, direction: &Direction) -> (Point, Direction) {
-            match direction {
-                Up => todo!(),
-                Right => todo!(),
-                Down => todo!(),
-                Left => (
-                    Point {
-                        x: point.y + 4,
-                        y: 4,
-                    },
-                    Down,
-                ),
-            }
-        }
+    #[test]
+    fn test_2() {
+        assert_eq!(solve_part_2(include_str!("../inputs/prob22")), 123046);
     }
 
     const INPUT_2: &'static str = r"        ...#
@@ -33,39 +33,11 @@ fn solve(input: &str) -> (usize, usize) {
                 targets.insert(next.pos);
             }
 
-            let up_pos = next.pos.up();
-            if let Some(up) = grid.get(up_pos) {
-                if *up == next.value + 1 {
+            for n in grid.neighbours4(next.pos) {
+                if *n.value == next.value + 1 {
                     queue.push_back(Point {
-                        value: *up,
-                        pos: up_pos,
-                    });
-                }
-            }
-            let down_pos = next.pos.down();
-            if let Some(down) = grid.get(down_pos) {
-                if *down == next.value + 1 {
-                    queue.push_back(Point {
-                        value: *down,
-                        pos: down_pos,
-                    });
-                }
-            }
-            let left_pos = next.pos.left();
-            if let Some(left) = grid.get(left_pos) {
-                if *left == next.value + 1 {
-                    queue.push_back(Point {
-                        value: *left,
-                        pos: left_pos,
-                    });
-                }
-            }
-            let right_pos = next.pos.right();
-            if let Some(right) = grid.get(right_pos) {
-                if *right == next.value + 1 {
-                    queue.push_back(Point {
-                        value: *right,
-                        pos: right_pos,
+                        value: *n.value,
+                        pos: n.pos,
                     });
                 }
             }
@@ -101,20 +101,7 @@ struct Robot<const ROWS: usize, const COLS: usize> {
 }
 impl<const ROWS: usize, const COLS: usize> Robot<ROWS, COLS> {
     fn step(&mut self) {
-        let new_row = (self.pos.row() as isize) + self.velocity.0;
-        let new_row = if new_row < 0 {
-            (ROWS as isize + new_row) as usize
-        } else {
-            new_row as usize % ROWS
-        };
-        let new_col = (self.pos.col() as isize) + self.velocity.1;
-        let new_col = if new_col < 0 {
-            (COLS as isize + new_col) as usize
-        } else {
-            new_col as usize % COLS
-        };
-
-        self.pos = Pos::new(new_row, new_col)
+        self.pos = self.pos.offset_wrapping(self.velocity, ROWS, COLS);
     }
 }
 
@@ -1,11 +1,14 @@
 use std::collections::{HashMap, VecDeque};
 
-use aoc_lib::grid::{Grid, Point, Pos};
+use aoc_lib::{
+    char_enum,
+    grid::{Grid, Point, Pos},
+};
 use itertools::Itertools;
 
 pub fn solve_part_1(input: &str) -> usize {
     let (grid_str, steps_str) = input.split("\n\n").collect_tuple::<(_, _)>().unwrap();
-    let robot = Robot::new(grid_str.parse::<Grid<char>>().unwrap());
+    let robot = Robot::new(grid_str);
 
     solve(robot, steps_str)
 }
@@ -26,7 +29,7 @@ pub fn solve_part_2(input: &str) -> usize {
                 .collect::<String>()
         })
         .join("\n");
-    let robot = Robot::new(grid_str.parse::<Grid<char>>().unwrap());
+    let robot = Robot::new(&grid_str);
     solve(robot, steps_str)
 }
 
@@ -42,7 +45,7 @@ fn solve(mut robot: Robot, steps: &str) -> usize {
     for b in robot
         .grid
         .iter()
-        .filter(|p| p.value == '[' || p.value == 'O')
+        .filter(|p| p.value == Tile::BoxLeft || p.value == Tile::Box)
     {
         result += b.pos.row() * 100 + b.pos.col();
     }
@@ -50,19 +53,30 @@ fn solve(mut robot: Robot, steps: &str) -> usize {
     result
 }
 
+char_enum! {
+    #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+    enum Tile {
+        Wall = '#',
+        Floor = '.',
+        Box = 'O',
+        BoxLeft = '[',
+        BoxRight = ']',
+        Robot = '@',
+    }
+}
+
 struct Robot {
-    grid: Grid<char>,
+    grid: Grid<Tile>,
     current_pos: Pos,
 }
 
 impl Robot {
-    fn new(grid: Grid<char>) -> Self {
-        let current_pos = grid
-            .iter()
-            .find(|p| p.value == '@')
-            .unwrap_or_else(|| panic!("No robot found"))
-            .pos;
-        Self { grid, current_pos }
+    fn new(input: &str) -> Self {
+        let (grid, markers) = Grid::parse_with_markers(input, &['@'], None).unwrap();
+        Self {
+            grid,
+            current_pos: markers[&'@'],
+        }
     }
     fn step(&mut self, step: char) {
         let mut iter = match step {
@@ -75,48 +89,48 @@ impl Robot {
         iter.next();
 
         if let Some(next) = iter.next() {
-            if next.value == '#' {
+            if next.value == Tile::Wall {
                 return;
             }
 
-            if next.value == '.' {
-                self.grid.insert(next.pos, '@');
-                self.grid.insert(self.current_pos, '.');
+            if next.value == Tile::Floor {
+                self.grid.insert(next.pos, Tile::Robot);
+                self.grid.insert(self.current_pos, Tile::Floor);
                 self.current_pos = next.pos;
                 return;
             }
 
-            if next.value == 'O' {
+            if next.value == Tile::Box {
                 let first_box = next.pos;
                 for p in iter {
                     match p.value {
-                        '#' => break,
-                        '.' => {
-                            self.grid.insert(first_box, '@');
-                            self.grid.insert(self.current_pos, '.');
-                            self.grid.insert(p.pos, 'O');
+                        Tile::Wall => break,
+                        Tile::Floor => {
+                            self.grid.insert(first_box, Tile::Robot);
+                            self.grid.insert(self.current_pos, Tile::Floor);
+                            self.grid.insert(p.pos, Tile::Box);
                             self.current_pos = first_box;
                             return;
                         }
                         _ => {}
                     }
                 }
-            } else if next.value == ']' || next.value == '[' {
+            } else if next.value == Tile::BoxRight || next.value == Tile::BoxLeft {
                 if step == '<' || step == '>' {
                     let mut stack = VecDeque::new();
                     let first_box = next.pos;
                     stack.push_back(next);
                     for p in iter {
                         match p.value {
-                            '#' => break,
-                            '.' => {
+                            Tile::Wall => break,
+                            Tile::Floor => {
                                 let mut current = p.pos;
                                 while let Some(prev) = stack.pop_back() {
                                     self.grid.insert(current, prev.value);
                                     current = prev.pos;
                                 }
-                                self.grid.insert(self.current_pos, '.');
-                                self.grid.insert(first_box, '@');
+                                self.grid.insert(self.current_pos, Tile::Floor);
+                                self.grid.insert(first_box, Tile::Robot);
                                 self.current_pos = first_box;
                                 return;
                             }
@@ -127,23 +141,23 @@ impl Robot {
                     let mut boxes = VecDeque::new();
                     let first_box = next.pos;
                     boxes.push_back(next);
-                    let mut boxes_to_move = HashMap::<Pos, char>::new();
+                    let mut boxes_to_move = HashMap::<Pos, Tile>::new();
 
                     while let Some(b) = boxes.pop_front() {
                         //Check neighbour...
-                        if b.value == ']' {
+                        if b.value == Tile::BoxRight {
                             let pos = b.pos.left();
                             if !boxes_to_move.contains_key(&pos) {
                                 boxes.push_back(Point {
-                                    value: *self.grid.get(pos).expect("Must be"),
+                                    value: self.grid[pos],
                                     pos,
                                 })
                             }
-                        } else if b.value == '[' {
+                        } else if b.value == Tile::BoxLeft {
                             let pos = b.pos.right();
                             if !boxes_to_move.contains_key(&pos) {
                                 boxes.push_back(Point {
-                                    value: *self.grid.get(pos).expect("Must be"),
+                                    value: self.grid[pos],
                                     pos,
                                 })
                             }
@@ -154,10 +168,10 @@ impl Robot {
                         } else {
                             b.pos.down()
                         };
-                        let up_down_val = *self.grid.get(up_down_pos).expect("Must be");
+                        let up_down_val = self.grid[up_down_pos];
                         match up_down_val {
-                            '.' => {}
-                            '[' | ']' => boxes.push_back(Point {
+                            Tile::Floor => {}
+                            Tile::BoxLeft | Tile::BoxRight => boxes.push_back(Point {
                                 value: up_down_val,
                                 pos: up_down_pos,
                             }),
@@ -180,8 +194,8 @@ impl Robot {
                             Pos::down,
                         );
                     }
-                    self.grid.insert(self.current_pos, '.');
-                    self.grid.insert(first_box, '@');
+                    self.grid.insert(self.current_pos, Tile::Floor);
+                    self.grid.insert(first_box, Tile::Robot);
                     self.current_pos = first_box;
                 }
             }
@@ -190,13 +204,13 @@ impl Robot {
 
     fn move_all<'a, I, F>(&mut self, iter: I, move_fn: F)
     where
-        I: Iterator<Item = (&'a Pos, &'a char)>,
+        I: Iterator<Item = (&'a Pos, &'a Tile)>,
         F: Fn(Pos) -> Pos,
     {
         for (&pos, &value) in iter {
             let new_pos = (move_fn)(pos);
             self.grid.insert(new_pos, value);
-            self.grid.insert(pos, '.');
+            self.grid.insert(pos, Tile::Floor);
         }
     }
 }
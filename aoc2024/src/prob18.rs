@@ -1,6 +1,6 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashSet;
 
-use aoc_lib::grid::Pos;
+use aoc_lib::grid::{search::bfs, Grid, Pos};
 
 pub fn solve_part_1(input: &str) -> usize {
     _solve_part_1(input, 71, 71, 1024)
@@ -75,57 +75,11 @@ fn _solve_part_2(input: &str, width: usize, height: usize) -> Pos {
 }
 
 fn find_path(bytes: &HashSet<Pos>, width: usize, height: usize) -> Option<usize> {
-    let mut nodes = VecDeque::new();
-    nodes.push_back((Pos::new(0, 0), 0));
-
-    let mut visited = HashSet::new();
-
-    while let Some((node, distance)) = nodes.pop_front() {
-        if node == Pos::new(height - 1, width - 1) {
-            return Some(distance);
-        }
-
-        //neighbours
-        if let Some(&up) = node.up().in_bounds(width, height) {
-            if !visited.contains(&up) && !bytes.contains(&up) {
-                visited.insert(up);
-                nodes.push_back((up, distance + 1));
-            }
-        }
-        if let Some(&right) = node.right().in_bounds(width, height) {
-            if !visited.contains(&right) && !bytes.contains(&right) {
-                visited.insert(right);
-                nodes.push_back((right, distance + 1));
-            }
-        }
-        if let Some(&down) = node.down().in_bounds(width, height) {
-            if !visited.contains(&down) && !bytes.contains(&down) {
-                visited.insert(down);
-                nodes.push_back((down, distance + 1));
-            }
-        }
-        if let Some(&left) = node.left().in_bounds(width, height) {
-            if !visited.contains(&left) && !bytes.contains(&left) {
-                visited.insert(left);
-                nodes.push_back((left, distance + 1));
-            }
-        }
-    }
-    None
-}
-
-trait PosInBounds {
-    fn in_bounds(&self, width: usize, height: usize) -> Option<&Self>;
-}
-
-impl PosInBounds for Pos {
-    fn in_bounds(&self, width: usize, height: usize) -> Option<&Self> {
-        if self.row() < height && self.col() < width {
-            Some(self)
-        } else {
-            None
-        }
+    let mut grid = Grid::new(height, width, false);
+    for &b in bytes {
+        grid.insert(b, true);
     }
+    bfs(&grid, [Pos::new(0, 0)], |_, to| !to.value)[Pos::new(height - 1, width - 1)]
 }
 
 #[cfg(test)]
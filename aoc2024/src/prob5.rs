@@ -1,4 +1,6 @@
-use aoc_lib::graph::{algorithms::topological_sort, AdjacencyListGraph};
+use std::collections::HashSet;
+
+use aoc_lib::graph::{algorithms::topological_sort_by, AdjacencyListGraph};
 use itertools::Itertools;
 
 pub fn solve_part_1(input: &str) -> usize {
@@ -62,6 +64,7 @@ pub fn solve_part_2(input: &str) -> usize {
                 .expect("Can not happen")
         })
         .collect::<Vec<_>>();
+    let graph = AdjacencyListGraph::new(rules.iter().copied());
 
     updates
         .lines()
@@ -73,15 +76,9 @@ pub fn solve_part_2(input: &str) -> usize {
         })
         .filter(|update| !is_valid(update, &rules))
         .map(|update| {
-            let graph = AdjacencyListGraph::new(
-                rules
-                    .iter()
-                    .filter(|(a, b)| update.iter().contains(a) && update.iter().contains(b))
-                    .copied()
-                    .collect_vec(),
-            );
-
-            topological_sort(graph)
+            let pages = update.iter().copied().collect::<HashSet<_>>();
+            topological_sort_by(&graph, Some(&pages), |a, b| a.cmp(b))
+                .expect("Rules for an update can't have a cycle")
         })
         .map(|update| {
             update
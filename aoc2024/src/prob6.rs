@@ -1,31 +1,30 @@
 use std::{collections::HashSet, iter::Peekable};
 
-use aoc_lib::grid::{Grid, PathIterator, Point, Pos};
+use aoc_lib::grid::{Direction, Grid, PathIterator, Point, Pos};
 
 pub fn solve_part_1(input: &str) -> usize {
-    let grid = input.parse::<Grid<char>>().expect("Invalid input");
-    GuardIter::start(&grid)
+    let (grid, start) = parse(input);
+    GuardIter::start(&grid, start)
         .map(|(p, _)| p.pos)
         .collect::<HashSet<_>>()
         .len()
 }
 
 pub fn solve_part_2(input: &str) -> usize {
-    let mut grid = input.parse::<Grid<char>>().expect("Invalid input");
+    let (mut grid, start) = parse(input);
 
-    let mut iter = GuardIter::start(&grid).peekable();
-
-    let start_pos = iter.peek().expect("No start pos found").0.pos;
-    let visited = iter.map(|(p, _)| p.pos).collect::<HashSet<_>>();
+    let visited = GuardIter::start(&grid, start)
+        .map(|(p, _)| p.pos)
+        .collect::<HashSet<_>>();
 
     let mut result = 0;
     for pos in visited {
-        if pos == start_pos {
+        if pos == start {
             continue;
         }
 
         grid.insert(pos, '#');
-        if has_cycle(&grid) {
+        if has_cycle(&grid, start) {
             result += 1;
         }
         grid.insert(pos, '.');
@@ -34,6 +33,12 @@ pub fn solve_part_2(input: &str) -> usize {
     result
 }
 
+fn parse(input: &str) -> (Grid<char>, Pos) {
+    let (grid, markers) =
+        Grid::parse_with_markers(input, &['^'], Some('.')).expect("Invalid input");
+    (grid, markers[&'^'])
+}
+
 struct GuardIter<'a> {
     grid: &'a Grid<char>,
     current_iter: Peekable<PathIterator<'a, char>>,
@@ -42,55 +47,29 @@ struct GuardIter<'a> {
 }
 
 impl<'a> GuardIter<'a> {
-    fn start(grid: &'a Grid<char>) -> Self {
-        let start_pos = grid
-            .iter()
-            .find(|point| point.value == '^')
-            .expect("No start pos found");
-
+    fn start(grid: &'a Grid<char>, start: Pos) -> Self {
         Self {
             grid,
-            current_iter: grid.up(start_pos.pos).peekable(),
-            current_pos: start_pos.pos,
+            current_iter: grid.up(start).peekable(),
+            current_pos: start,
             current_dir: Direction::Up,
         }
     }
 }
 
-#[repr(u8)]
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
-enum Direction {
-    Up = 0,
-    Right = 1,
-    Down = 2,
-    Left = 3,
-}
-
-impl Direction {
-    fn turn(self) -> Self {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        }
-    }
-}
-
 impl<'a> Iterator for GuardIter<'a> {
     type Item = (Point<char>, Direction);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let directions = [Grid::up, Grid::right, Grid::down, Grid::left];
-
         let iter = &mut self.current_iter;
 
         if let Some(peek) = iter.peek() {
             if peek.value == '#' {
-                self.current_dir = self.current_dir.turn();
-                let iter =
-                    (directions[self.current_dir as usize])(self.grid, self.current_pos).peekable();
-                self.current_iter = iter;
+                self.current_dir = self.current_dir.turn_cw();
+                self.current_iter = self
+                    .grid
+                    .path(self.current_dir, self.current_pos)
+                    .peekable();
                 return self.next();
             }
 
@@ -104,9 +83,9 @@ impl<'a> Iterator for GuardIter<'a> {
     }
 }
 
-fn has_cycle(grid: &Grid<char>) -> bool {
+fn has_cycle(grid: &Grid<char>, start: Pos) -> bool {
     let mut visited = HashSet::new();
-    for (p, dir) in GuardIter::start(grid) {
+    for (p, dir) in GuardIter::start(grid, start) {
         let pos = (p.pos, dir);
         if visited.contains(&pos) {
             return true;
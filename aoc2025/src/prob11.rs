@@ -1,34 +1,26 @@
-use aoc_lib::graph::{AdjacencyListGraph, DirectedGraph};
+use aoc_lib::graph::{
+    algorithms::{count_paths, count_paths_through, Waypoints},
+    AdjacencyListGraph,
+};
 
 pub fn solve_part_1(input: &str) -> usize {
-    let edges: Vec<(String, String)> = input
-        .lines()
-        .flat_map(|line| {
-            let (source, targets) = line.split_once(": ").unwrap();
-
-            targets
-                .split(" ")
-                .map(|t| (source.to_string(), t.to_string()))
-        })
-        .collect();
-
-    let graph = AdjacencyListGraph::new(edges);
-
-    dfs::<String>(&graph, &"you".to_string(), &"out".to_string())
+    let graph = parse(input);
+    count_paths(&graph, &"you".to_string(), &"out".to_string())
+        .expect("Should be able to count the paths") as usize
 }
 
-fn dfs<T: Eq + Sized + 'static>(graph: &AdjacencyListGraph<T>, start: &T, end: &T) -> usize {
-    if start == end {
-        return 1;
-    }
-    let mut count = 0;
-    for (_x, to) in graph.outgoing_edges(start) {
-        count += dfs::<T>(graph, to, end);
-    }
-    count
+pub fn solve_part_2(input: &str) -> usize {
+    let graph = parse(input);
+    count_paths_through(
+        &graph,
+        &"svr".to_string(),
+        &"out".to_string(),
+        Waypoints::AnyOrder(&["dac".to_string(), "fft".to_string()]),
+    )
+    .expect("Should be able to count the paths") as usize
 }
 
-pub fn solve_part_2(input: &str) -> usize {
+fn parse(input: &str) -> AdjacencyListGraph<String> {
     let edges: Vec<(String, String)> = input
         .lines()
         .flat_map(|line| {
@@ -40,62 +32,7 @@ pub fn solve_part_2(input: &str) -> usize {
         })
         .collect();
 
-    let graph = AdjacencyListGraph::new(edges);
-    part2::solve(&graph)
-}
-
-mod part2 {
-    use std::collections::HashMap;
-
-    use aoc_lib::graph::{algorithms::topological_sort, AdjacencyListGraph, DirectedGraph};
-
-    pub fn solve(graph: &AdjacencyListGraph<String>) -> usize {
-        let mut nodes = HashMap::<&str, NodeResult>::new();
-        let mut out = NodeResult::default();
-        out.paths_both = 1;
-        nodes.insert("out", out);
-
-        let order = topological_sort(graph.clone());
-        for node in order.iter().filter(|n| *n != "out").rev() {
-            let mut result = NodeResult::default();
-            for (_, n) in graph.outgoing_edges(node) {
-                let Some(neighbour_result) = nodes.get(n.as_str()) else {
-                    panic!();
-                };
-                result.paths_none += neighbour_result.paths_none;
-                result.paths_fft += neighbour_result.paths_fft + neighbour_result.paths_none;
-                result.paths_dac += neighbour_result.paths_dac + neighbour_result.paths_none;
-                result.paths_both += neighbour_result.paths_both
-                    + neighbour_result.paths_fft
-                    + neighbour_result.paths_dac
-                    + neighbour_result.paths_none;
-
-                if node == "dac" {
-                    result.paths_none += neighbour_result.paths_dac;
-                    result.paths_fft += neighbour_result.paths_both;
-                }
-                if node == "fft" {
-                    result.paths_none += neighbour_result.paths_fft;
-                    result.paths_dac += neighbour_result.paths_both;
-                }
-            }
-
-            nodes.insert(node.as_str(), result);
-        }
-
-        nodes
-            .get("svr")
-            .expect("All nodes should be there")
-            .paths_none
-    }
-
-    #[derive(Default, Debug)]
-    pub struct NodeResult {
-        paths_none: usize,
-        paths_dac: usize,
-        paths_fft: usize,
-        paths_both: usize,
-    }
+    AdjacencyListGraph::new(edges)
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use aoc_lib::grid::{Direction, Grid, Point};
+use aoc_lib::grid::{Grid, Point};
 
 pub fn solve_part_1(input: &str) -> usize {
     let grid: Grid<char> = input.parse().unwrap();
@@ -24,27 +24,10 @@ pub fn solve_part_2(input: &str) -> usize {
 
 fn all_removable_rolls<'a>(grid: &'a Grid<char>) -> impl Iterator<Item = Point<char>> + 'a {
     grid.iter().filter(|p| p.value == '@').filter(|point| {
-        let mut roll_neighbours = 0;
-        for dir in [
-            Direction::Up,
-            Direction::UpRight,
-            Direction::Right,
-            Direction::DownRight,
-            Direction::Down,
-            Direction::DownLeft,
-            Direction::Left,
-            Direction::UpLeft,
-        ] {
-            if grid
-                .path(dir, point.pos)
-                .nth(1)
-                .map(|p| p.value == '@')
-                .unwrap_or(false)
-            {
-                roll_neighbours += 1;
-            }
-        }
-        roll_neighbours < 4
+        grid.neighbours8(point.pos)
+            .filter(|n| *n.value == '@')
+            .count()
+            < 4
     })
 }
 
@@ -1,29 +1,23 @@
-use std::ops::RangeInclusive;
+use aoc_lib::interval::IntervalSet;
 
 pub fn solve_part_1(input: &str) -> usize {
     let (ranges_in, ids_in) = input.split_once("\n\n").unwrap();
-
-    let ranges: Vec<_> = ranges_in
-        .lines()
-        .map(|line| {
-            let (low, high) = line.split_once("-").unwrap();
-            let low = low.parse::<u64>().unwrap();
-            let high = high.parse::<u64>().unwrap();
-            low..=high
-        })
-        .collect();
+    let fresh = parse_ranges(ranges_in);
 
     ids_in
         .lines()
         .map(|id| id.parse::<u64>().unwrap())
-        .filter(|id| ranges.iter().any(|r| r.contains(id)))
+        .filter(|&id| fresh.contains(id))
         .count()
 }
 
 pub fn solve_part_2(input: &str) -> usize {
     let (ranges_in, _) = input.split_once("\n\n").unwrap();
+    parse_ranges(ranges_in).len() as usize
+}
 
-    let mut ranges: Vec<_> = ranges_in
+fn parse_ranges(input: &str) -> IntervalSet<u64> {
+    input
         .lines()
         .map(|line| {
             let (low, high) = line.split_once("-").unwrap();
@@ -31,43 +25,7 @@ pub fn solve_part_2(input: &str) -> usize {
             let high = high.parse::<u64>().unwrap();
             low..=high
         })
-        .collect();
-
-    let mut reduced_ranges = Vec::with_capacity(ranges.len());
-
-    while let Some(current) = ranges.pop() {
-        // Try merge it with any in the list and replace those
-        if let Some((idx, merged)) = merge_ranges(&ranges, &current) {
-            ranges[idx] = merged;
-        } else {
-            reduced_ranges.push(current);
-        }
-    }
-
-    reduced_ranges.into_iter().map(|r| r.count()).sum()
-}
-
-fn merge_ranges(
-    ranges: &[RangeInclusive<u64>],
-    current: &RangeInclusive<u64>,
-) -> Option<(usize, RangeInclusive<u64>)> {
-    for (idx, r) in ranges.iter().enumerate() {
-        if r.contains(current.start()) && r.contains(current.end()) {
-            // current is completely inside r
-            return Some((idx, r.clone()));
-        }
-        if current.contains(r.start()) && current.contains(r.end()) {
-            return Some((idx, current.clone()));
-        }
-        if r.contains(current.start()) {
-            return Some((idx, *r.start()..=*current.end()));
-        }
-        if r.contains(current.end()) {
-            return Some((idx, *current.start()..=*r.end()));
-        }
-        // No match
-    }
-    None
+        .collect()
 }
 
 #[cfg(test)]